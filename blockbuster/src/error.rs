@@ -1,3 +1,4 @@
+use solana_sdk::pubkey::Pubkey;
 use std::io::Error;
 use thiserror::Error;
 
@@ -25,6 +26,36 @@ pub enum BlockbusterError {
     AccountTypeNotImplemented,
     #[error("Could not deserialize data: {0}")]
     CustomDeserializationError(String),
+    #[error("Leaf hash does not match the changelog leaf for tree {tree} at seq {seq}")]
+    LeafHashMismatch {
+        tree: Pubkey,
+        seq: u64,
+        computed: [u8; 32],
+        changelog: [u8; 32],
+    },
+    #[error("Data hash recomputed from the metadata does not match the leaf")]
+    DataHashMismatch { computed: [u8; 32], leaf: [u8; 32] },
+    #[error("Creator hash recomputed from the metadata does not match the leaf")]
+    CreatorHashMismatch { computed: [u8; 32], leaf: [u8; 32] },
+    #[error("Instruction is missing the `{account}` account of {accounts}")]
+    MissingInstructionAccount {
+        accounts: &'static str,
+        account: &'static str,
+    },
+    #[error("Changelog for tree {tree} has {actual} path nodes, expected {expected}")]
+    ChangeLogDepthMismatch {
+        tree: Pubkey,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Tree depth {0} exceeds the maximum depth")]
+    TreeDepthTooLarge(u32),
+    #[error("Leaf index {index} is out of range for a tree of depth {depth}")]
+    LeafIndexOutOfRange { index: u32, depth: u32 },
+    #[error("Proof has {actual} nodes, expected {expected}")]
+    ProofLengthMismatch { expected: usize, actual: usize },
+    #[error("Canopy of {0} nodes does not hold complete levels")]
+    InvalidCanopyLength(usize),
 }
 
 impl From<std::io::Error> for BlockbusterError {
@@ -1,6 +1,9 @@
 use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
 use solana_transaction_status::InnerInstructions;
-use std::collections::{HashSet, VecDeque};
+use std::{
+    collections::{HashSet, VecDeque},
+    ops::Range,
+};
 
 pub type IxPair<'a> = (Pubkey, &'a CompiledInstruction);
 
@@ -37,15 +40,14 @@ pub fn order_instructions<'a>(
 
     // Get inner instructions.
     for (outer_instruction_index, message_instruction) in message_instructions.iter().enumerate() {
-        let non_hoisted_inner_instruction = meta_inner_instructions
-            .iter()
-            .filter_map(|ix| (ix.index == outer_instruction_index as u8).then(|| &ix.instructions))
-            .flatten()
-            .map(|inner_ix| {
-                let cix = &inner_ix.instruction;
-                (account_keys[cix.program_id_index as usize], cix)
-            })
-            .collect::<Vec<IxPair>>();
+        let non_hoisted_inner_instruction = inner_instructions(
+            account_keys,
+            meta_inner_instructions,
+            outer_instruction_index,
+        )
+        .into_iter()
+        .map(|(ix_pair, _)| ix_pair)
+        .collect::<Vec<IxPair>>();
 
         let hoisted = hoist_known_programs(programs, &non_hoisted_inner_instruction);
         ordered_ixs.extend(hoisted);
@@ -66,23 +68,54 @@ pub fn order_instructions<'a>(
     ordered_ixs
 }
 
-fn hoist_known_programs<'a>(
+/// The inner instructions of the outer instruction at `outer_instruction_index`, in execution
+/// order, with their stack height when the transaction recorded it.
+pub(crate) fn inner_instructions<'a>(
+    account_keys: &[Pubkey],
+    meta_inner_instructions: &'a [InnerInstructions],
+    outer_instruction_index: usize,
+) -> Vec<(IxPair<'a>, Option<u32>)> {
+    meta_inner_instructions
+        .iter()
+        .filter(|ix| usize::from(ix.index) == outer_instruction_index)
+        .flat_map(|ix| ix.instructions.iter())
+        .map(|inner_ix| {
+            let cix = &inner_ix.instruction;
+            (
+                (account_keys[cix.program_id_index as usize], cix),
+                inner_ix.stack_height,
+            )
+        })
+        .collect()
+}
+
+/// Positions of the inner instructions of programs in `programs`, which `order_instructions`
+/// hoists, each with the range of inner instructions listed under it: the following ones up to
+/// the next call to the same program.
+pub(crate) fn hoisted_ranges(
     programs: &HashSet<Pubkey>,
-    ix_pairs: &[IxPair<'a>],
-) -> Vec<(IxPair<'a>, Option<Vec<IxPair<'a>>>)> {
+    ix_pairs: &[IxPair],
+) -> Vec<(usize, Range<usize>)> {
     ix_pairs
         .iter()
         .enumerate()
-        .filter_map(|(index, &(pid, ci))| {
-            programs.contains(&pid).then(|| {
-                let inner_copy = ix_pairs
-                    .iter()
-                    .skip(index + 1)
-                    .take_while(|&&(inner_pid, _)| inner_pid != pid)
-                    .cloned()
-                    .collect::<Vec<IxPair<'a>>>();
-                ((pid, ci), Some(inner_copy))
-            })
+        .filter(|(_, (pid, _))| programs.contains(pid))
+        .map(|(index, &(pid, _))| {
+            let end = ix_pairs[index + 1..]
+                .iter()
+                .position(|&(inner_pid, _)| inner_pid == pid)
+                .map_or(ix_pairs.len(), |offset| index + 1 + offset);
+            (index, index + 1..end)
         })
         .collect()
 }
+
+fn hoist_known_programs<'a>(
+    programs: &HashSet<Pubkey>,
+    ix_pairs: &[IxPair<'a>],
+) -> Vec<(IxPair<'a>, Option<Vec<IxPair<'a>>>)> {
+    hoisted_ranges(programs, ix_pairs)
+        .into_iter()
+        .map(|(index, inner)| (ix_pairs[index], Some(ix_pairs[inner].to_vec())))
+        .collect()
+}
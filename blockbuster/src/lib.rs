@@ -1,6 +1,8 @@
 pub mod error;
 pub mod instruction;
+pub mod noop;
 pub mod program_handler;
 pub mod programs;
+pub mod tree;
 
 pub use mpl_token_metadata as token_metadata;
@@ -0,0 +1,171 @@
+use crate::instruction::{hoisted_ranges, inner_instructions, IxPair};
+use borsh::BorshDeserialize;
+use log::warn;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey, pubkeys};
+use solana_transaction_status::InnerInstructions;
+use spl_account_compression::events::AccountCompressionEvent;
+use std::{collections::HashSet, ops::Range};
+
+pubkeys!(mpl_noop_id, "mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3");
+
+pubkeys!(
+    mpl_account_compression_id,
+    "mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW"
+);
+
+/// The noop program families account compression events are logged through.
+///
+/// Bubblegum V2 trees are managed by Metaplex's forks of account compression and noop, which emit
+/// the same events as the SPL programs under different program IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NoopProgram {
+    /// `spl-noop`, paired with `spl-account-compression`.
+    Spl,
+    /// `mpl-noop`, paired with `mpl-account-compression`.
+    Mpl,
+}
+
+impl NoopProgram {
+    pub fn from_program_id(program_id: &Pubkey) -> Option<Self> {
+        if program_id == &spl_noop::id() {
+            Some(NoopProgram::Spl)
+        } else if program_id == &mpl_noop_id() {
+            Some(NoopProgram::Mpl)
+        } else {
+            None
+        }
+    }
+
+    pub fn id(&self) -> Pubkey {
+        match self {
+            NoopProgram::Spl => spl_noop::id(),
+            NoopProgram::Mpl => mpl_noop_id(),
+        }
+    }
+
+    /// The account compression program that logs through this noop program.
+    pub fn account_compression_id(&self) -> Pubkey {
+        match self {
+            NoopProgram::Spl => spl_account_compression::id(),
+            NoopProgram::Mpl => mpl_account_compression_id(),
+        }
+    }
+}
+
+/// An account compression event logged through a noop program.
+pub struct NoopEvent {
+    /// The program of the ordered instruction the event was logged under.
+    pub program: Pubkey,
+    /// Position of that instruction in the `order_instructions` output.
+    pub instruction_index: usize,
+    /// Position of the noop instruction in the inner instructions of that instruction.
+    pub inner_ix_index: usize,
+    pub noop_program: NoopProgram,
+    pub event: AccountCompressionEvent,
+}
+
+/// Decodes every noop event of a transaction, in execution order.
+///
+/// Each event is reported under the instruction of the `order_instructions` output, for the same
+/// arguments, that invoked the noop instruction: the closest hoisted inner instruction of a
+/// program in `programs` in its invocation chain, otherwise the outer instruction. The chain is
+/// read from the stack heights of the inner instructions. Transactions recorded without stack
+/// heights fall back to the closest hoisted instruction whose inner instructions include the noop
+/// instruction, which cannot tell a noop call made after that program returned from one made
+/// under it.
+///
+/// Noop instructions that are not under any ordered instruction, or whose data is not an
+/// `AccountCompressionEvent`, are skipped.
+pub fn noop_events(
+    programs: &HashSet<Pubkey>,
+    account_keys: &[Pubkey],
+    message_instructions: &[CompiledInstruction],
+    meta_inner_instructions: &[InnerInstructions],
+) -> Vec<NoopEvent> {
+    let mut events = Vec::new();
+    // Position in the `order_instructions` output of the first instruction of the outer
+    // instruction, which lists its hoisted inner instructions before itself.
+    let mut first_instruction_index = 0;
+    for (outer_instruction_index, message_instruction) in message_instructions.iter().enumerate() {
+        let (ix_pairs, stack_heights): (Vec<IxPair>, Vec<Option<u32>>) = inner_instructions(
+            account_keys,
+            meta_inner_instructions,
+            outer_instruction_index,
+        )
+        .into_iter()
+        .unzip();
+        let hoisted = hoisted_ranges(programs, &ix_pairs);
+        let outer_program = account_keys
+            .get(message_instruction.program_id_index as usize)
+            .filter(|pid| programs.contains(pid));
+
+        for (position, (pid, cix)) in ix_pairs.iter().enumerate() {
+            let Some(noop_program) = NoopProgram::from_program_id(pid) else {
+                continue;
+            };
+            if cix.data.is_empty() {
+                continue;
+            }
+            let (program, instruction_index, inner_ix_index) =
+                match ordered_invoker(&stack_heights, &hoisted, position) {
+                    Some(hoisted_index) => {
+                        let (start, inner) = &hoisted[hoisted_index];
+                        (
+                            ix_pairs[*start].0,
+                            first_instruction_index + hoisted_index,
+                            position - inner.start,
+                        )
+                    }
+                    None => match outer_program {
+                        Some(program) => {
+                            (*program, first_instruction_index + hoisted.len(), position)
+                        }
+                        None => continue,
+                    },
+                };
+            match AccountCompressionEvent::try_from_slice(&cix.data) {
+                Ok(event) => events.push(NoopEvent {
+                    program,
+                    instruction_index,
+                    inner_ix_index,
+                    noop_program,
+                    event,
+                }),
+                Err(e) => warn!("Error while deserializing noop data: {:?}", e),
+            }
+        }
+        first_instruction_index += hoisted.len() + usize::from(outer_program.is_some());
+    }
+    events
+}
+
+// Index in `hoisted` of the instruction the inner instruction at `position` is reported under, or
+// `None` for the outer instruction.
+fn ordered_invoker(
+    stack_heights: &[Option<u32>],
+    hoisted: &[(usize, Range<usize>)],
+    position: usize,
+) -> Option<usize> {
+    let Some(mut height) = stack_heights[position] else {
+        return hoisted
+            .iter()
+            .rposition(|(_, inner)| inner.contains(&position));
+    };
+    // The outer instruction runs at stack height 1, its invoker is the closest preceding inner
+    // instruction one level up.
+    let mut callee = position;
+    while height > 2 {
+        let caller = (0..callee)
+            .rev()
+            .find(|&index| stack_heights[index] == Some(height - 1))?;
+        if let Some(hoisted_index) = hoisted
+            .iter()
+            .position(|(start, inner)| *start == caller && inner.contains(&position))
+        {
+            return Some(hoisted_index);
+        }
+        callee = caller;
+        height -= 1;
+    }
+    None
+}
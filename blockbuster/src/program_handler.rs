@@ -36,6 +36,11 @@ impl ParseResult for NotUsed {
 
 pub trait ProgramParser: Sync + Send {
     fn key(&self) -> Pubkey;
+    /// Every program ID the parser handles, for registering it under each of them. Defaults to
+    /// `key()`.
+    fn keys(&self) -> Vec<Pubkey> {
+        vec![self.key()]
+    }
     fn key_match(&self, key: &Pubkey) -> bool;
     fn handles_instructions(&self) -> bool;
     fn handles_account_updates(&self) -> bool;
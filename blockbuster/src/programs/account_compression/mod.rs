@@ -0,0 +1,320 @@
+use crate::{
+    error::BlockbusterError,
+    instruction::InstructionBundle,
+    noop::{mpl_account_compression_id, NoopProgram},
+    program_handler::{ParseResult, ProgramParser},
+    programs::ProgramParseResult,
+};
+use anchor_lang::Discriminator;
+use borsh::BorshDeserialize;
+use log::warn;
+use solana_sdk::pubkey::Pubkey;
+use spl_account_compression::{
+    events::{AccountCompressionEvent, ChangeLogEvent, ChangeLogEventV1},
+    instruction::{
+        Append, CloseEmptyTree, InitEmptyMerkleTree, InsertOrAppend, ReplaceLeaf,
+        TransferAuthority, VerifyLeaf,
+    },
+};
+
+pub use spl_account_compression::id;
+
+mod state;
+
+pub use state::{
+    ChangeLog, ConcurrentMerkleTreeAccount, MerkleTreeHeader, MerkleTreeHeaderVersion,
+    MerkleTreePath,
+};
+
+/// SPL Account Compression instructions known to the parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstructionName {
+    Unknown,
+    InitEmptyMerkleTree,
+    ReplaceLeaf,
+    TransferAuthority,
+    VerifyLeaf,
+    Append,
+    InsertOrAppend,
+    CloseEmptyTree,
+}
+
+pub fn get_instruction_type(full_bytes: &[u8]) -> InstructionName {
+    let disc: [u8; 8] = match full_bytes.get(..8).and_then(|d| d.try_into().ok()) {
+        Some(disc) => disc,
+        None => return InstructionName::Unknown,
+    };
+    match disc {
+        InitEmptyMerkleTree::DISCRIMINATOR => InstructionName::InitEmptyMerkleTree,
+        ReplaceLeaf::DISCRIMINATOR => InstructionName::ReplaceLeaf,
+        TransferAuthority::DISCRIMINATOR => InstructionName::TransferAuthority,
+        VerifyLeaf::DISCRIMINATOR => InstructionName::VerifyLeaf,
+        Append::DISCRIMINATOR => InstructionName::Append,
+        InsertOrAppend::DISCRIMINATOR => InstructionName::InsertOrAppend,
+        CloseEmptyTree::DISCRIMINATOR => InstructionName::CloseEmptyTree,
+        _ => InstructionName::Unknown,
+    }
+}
+
+instruction_accounts! {
+    program = id();
+
+    /// Shared by `InitEmptyMerkleTree`, `ReplaceLeaf`, `Append` and `InsertOrAppend`.
+    pub struct ModifyTreeAccounts {
+        merkle_tree: required,
+        authority: required,
+        noop: required,
+    }
+
+    pub struct TransferAuthorityAccounts {
+        merkle_tree: required,
+        authority: required,
+    }
+
+    pub struct VerifyLeafAccounts {
+        merkle_tree: required,
+    }
+
+    pub struct CloseEmptyTreeAccounts {
+        merkle_tree: required,
+        authority: required,
+        recipient: required,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Payload {
+    InitEmptyMerkleTree {
+        merkle_tree: Pubkey,
+        authority: Pubkey,
+        max_depth: u32,
+        max_buffer_size: u32,
+    },
+    ReplaceLeaf {
+        merkle_tree: Pubkey,
+        authority: Pubkey,
+        root: [u8; 32],
+        previous_leaf: [u8; 32],
+        new_leaf: [u8; 32],
+        index: u32,
+    },
+    TransferAuthority {
+        merkle_tree: Pubkey,
+        authority: Pubkey,
+        new_authority: Pubkey,
+    },
+    VerifyLeaf {
+        merkle_tree: Pubkey,
+        root: [u8; 32],
+        leaf: [u8; 32],
+        index: u32,
+    },
+    Append {
+        merkle_tree: Pubkey,
+        authority: Pubkey,
+        leaf: [u8; 32],
+    },
+    InsertOrAppend {
+        merkle_tree: Pubkey,
+        authority: Pubkey,
+        root: [u8; 32],
+        leaf: [u8; 32],
+        index: u32,
+    },
+    CloseEmptyTree {
+        merkle_tree: Pubkey,
+        authority: Pubkey,
+        recipient: Pubkey,
+    },
+}
+
+pub struct AccountCompressionInstruction {
+    pub instruction: InstructionName,
+    pub payload: Option<Payload>,
+    /// The changelog written by the instruction. `TransferAuthority`, `VerifyLeaf` and
+    /// `CloseEmptyTree` do not modify the tree and emit none.
+    pub tree_update: Option<ChangeLogEventV1>,
+    /// The noop program that logged `tree_update`.
+    pub noop_program: Option<NoopProgram>,
+}
+
+impl AccountCompressionInstruction {
+    pub const fn new(ix: InstructionName) -> Self {
+        AccountCompressionInstruction {
+            instruction: ix,
+            payload: None,
+            tree_update: None,
+            noop_program: None,
+        }
+    }
+}
+
+impl ParseResult for AccountCompressionInstruction {
+    fn result_type(&self) -> ProgramParseResult {
+        ProgramParseResult::AccountCompression(self)
+    }
+    fn result(&self) -> &Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+}
+
+/// Parses both `spl-account-compression` and its `mpl-account-compression` fork, which share
+/// instructions and account layouts.
+pub struct AccountCompressionParser;
+
+impl ProgramParser for AccountCompressionParser {
+    /// The SPL program ID only. Parsers registered by `key()` miss `mpl-account-compression`
+    /// instructions, register this parser under each of `keys()` instead.
+    fn key(&self) -> Pubkey {
+        id()
+    }
+
+    fn keys(&self) -> Vec<Pubkey> {
+        vec![id(), mpl_account_compression_id()]
+    }
+
+    fn key_match(&self, key: &Pubkey) -> bool {
+        key == &id() || key == &mpl_account_compression_id()
+    }
+
+    fn handles_account_updates(&self) -> bool {
+        true
+    }
+
+    fn handles_instructions(&self) -> bool {
+        true
+    }
+
+    fn handle_account(
+        &self,
+        account_data: &[u8],
+    ) -> Result<Box<(dyn ParseResult + 'static)>, BlockbusterError> {
+        Ok(Box::new(ConcurrentMerkleTreeAccount::from_account_data(
+            account_data,
+        )?))
+    }
+
+    fn handle_instruction(
+        &self,
+        bundle: &InstructionBundle,
+    ) -> Result<Box<(dyn ParseResult + 'static)>, BlockbusterError> {
+        let InstructionBundle {
+            txn_id,
+            instruction,
+            inner_ix,
+            keys,
+            ..
+        } = bundle;
+        let outer_ix_data = match instruction {
+            Some(cix) => cix.data.as_ref(),
+            _ => return Err(BlockbusterError::DeserializationError),
+        };
+        let mut ac_inst = AccountCompressionInstruction::new(get_instruction_type(outer_ix_data));
+
+        // The changelog is emitted by the first noop CPI following the instruction.
+        if let Some(ixs) = inner_ix {
+            for (pid, cix) in ixs.iter() {
+                let Some(noop_program) = NoopProgram::from_program_id(pid) else {
+                    continue;
+                };
+                if !cix.data.is_empty() {
+                    match AccountCompressionEvent::try_from_slice(&cix.data) {
+                        Ok(AccountCompressionEvent::ChangeLog(ChangeLogEvent::V1(changelog))) => {
+                            ac_inst.tree_update = Some(changelog);
+                            ac_inst.noop_program = Some(noop_program);
+                            break;
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            warn!(
+                                "Error while deserializing txn {:?} with noop data: {:?}",
+                                txn_id, e
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        if outer_ix_data.len() >= 8 {
+            let ix_data = &outer_ix_data[8..];
+            ac_inst.payload = match ac_inst.instruction {
+                InstructionName::InitEmptyMerkleTree => {
+                    let accounts = ModifyTreeAccounts::try_from_keys(keys)?;
+                    let args = InitEmptyMerkleTree::try_from_slice(ix_data)?;
+                    Some(Payload::InitEmptyMerkleTree {
+                        merkle_tree: accounts.merkle_tree,
+                        authority: accounts.authority,
+                        max_depth: args.max_depth,
+                        max_buffer_size: args.max_buffer_size,
+                    })
+                }
+                InstructionName::ReplaceLeaf => {
+                    let accounts = ModifyTreeAccounts::try_from_keys(keys)?;
+                    let args = ReplaceLeaf::try_from_slice(ix_data)?;
+                    Some(Payload::ReplaceLeaf {
+                        merkle_tree: accounts.merkle_tree,
+                        authority: accounts.authority,
+                        root: args.root,
+                        previous_leaf: args.previous_leaf,
+                        new_leaf: args.new_leaf,
+                        index: args.index,
+                    })
+                }
+                InstructionName::TransferAuthority => {
+                    let accounts = TransferAuthorityAccounts::try_from_keys(keys)?;
+                    let args = TransferAuthority::try_from_slice(ix_data)?;
+                    Some(Payload::TransferAuthority {
+                        merkle_tree: accounts.merkle_tree,
+                        authority: accounts.authority,
+                        new_authority: args.new_authority,
+                    })
+                }
+                InstructionName::VerifyLeaf => {
+                    let accounts = VerifyLeafAccounts::try_from_keys(keys)?;
+                    let args = VerifyLeaf::try_from_slice(ix_data)?;
+                    Some(Payload::VerifyLeaf {
+                        merkle_tree: accounts.merkle_tree,
+                        root: args.root,
+                        leaf: args.leaf,
+                        index: args.index,
+                    })
+                }
+                InstructionName::Append => {
+                    let accounts = ModifyTreeAccounts::try_from_keys(keys)?;
+                    let args = Append::try_from_slice(ix_data)?;
+                    Some(Payload::Append {
+                        merkle_tree: accounts.merkle_tree,
+                        authority: accounts.authority,
+                        leaf: args.leaf,
+                    })
+                }
+                InstructionName::InsertOrAppend => {
+                    let accounts = ModifyTreeAccounts::try_from_keys(keys)?;
+                    let args = InsertOrAppend::try_from_slice(ix_data)?;
+                    Some(Payload::InsertOrAppend {
+                        merkle_tree: accounts.merkle_tree,
+                        authority: accounts.authority,
+                        root: args.root,
+                        leaf: args.leaf,
+                        index: args.index,
+                    })
+                }
+                InstructionName::CloseEmptyTree => {
+                    let accounts = CloseEmptyTreeAccounts::try_from_keys(keys)?;
+                    Some(Payload::CloseEmptyTree {
+                        merkle_tree: accounts.merkle_tree,
+                        authority: accounts.authority,
+                        recipient: accounts.recipient,
+                    })
+                }
+                InstructionName::Unknown => None,
+            };
+        }
+
+        Ok(Box::new(ac_inst))
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{error::BlockbusterError, program_handler::ParseResult, programs::ProgramParseResult};
+use borsh::BorshDeserialize;
+use solana_sdk::{clock::Slot, pubkey::Pubkey};
+use spl_account_compression::state::{
+    CompressionAccountType, ConcurrentMerkleTreeHeader, ConcurrentMerkleTreeHeaderData,
+    CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
+
+// `ConcurrentMerkleTreeHeaderDataV1` has no accessor for the authority, it follows the account
+// type, the header version, `max_buffer_size` and `max_depth`.
+const HEADER_V1_AUTHORITY_OFFSET: usize = 10;
+const NODE_SIZE: usize = 32;
+
+/// Version of a merkle tree account header, `ConcurrentMerkleTreeHeaderData` upstream.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MerkleTreeHeaderVersion {
+    V1,
+}
+
+/// Header of a `spl_account_compression` merkle tree account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleTreeHeader {
+    pub version: MerkleTreeHeaderVersion,
+    pub max_buffer_size: u32,
+    pub max_depth: u32,
+    pub authority: Pubkey,
+    pub creation_slot: Slot,
+}
+
+impl MerkleTreeHeader {
+    /// Decodes the header at the start of `account_data` with
+    /// `spl_account_compression::state::ConcurrentMerkleTreeHeader`.
+    pub fn from_account_data(account_data: &[u8]) -> Result<Self, BlockbusterError> {
+        if account_data.len() < CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 {
+            return Err(BlockbusterError::InvalidDataLength);
+        }
+        let header = ConcurrentMerkleTreeHeader::deserialize(&mut &account_data[..])
+            .map_err(|_| BlockbusterError::InvalidAccountType)?;
+        match header.account_type {
+            CompressionAccountType::ConcurrentMerkleTree => {}
+            CompressionAccountType::Uninitialized => {
+                return Err(BlockbusterError::UninitializedAccount)
+            }
+        }
+
+        let version = match header.header {
+            ConcurrentMerkleTreeHeaderData::V1(_) => MerkleTreeHeaderVersion::V1,
+        };
+        let mut authority = &account_data[HEADER_V1_AUTHORITY_OFFSET..];
+        Ok(MerkleTreeHeader {
+            version,
+            max_buffer_size: header.get_max_buffer_size(),
+            max_depth: header.get_max_depth(),
+            authority: Pubkey::deserialize(&mut authority)?,
+            creation_slot: header.get_creation_slot(),
+        })
+    }
+
+    /// Size of the encoded header, before the tree body.
+    pub const fn size(&self) -> usize {
+        match self.version {
+            MerkleTreeHeaderVersion::V1 => CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeLog {
+    pub root: [u8; 32],
+    /// Nodes from the leaf up to, but excluding, the root.
+    pub path: Vec<[u8; 32]>,
+    pub index: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleTreePath {
+    pub proof: Vec<[u8; 32]>,
+    pub leaf: [u8; 32],
+    pub index: u32,
+}
+
+/// A decoded `ConcurrentMerkleTree` account: the header, the tree body and the canopy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConcurrentMerkleTreeAccount {
+    pub header: MerkleTreeHeader,
+    pub sequence_number: u64,
+    /// Position of the most recent changelog in `change_logs`.
+    pub active_index: u64,
+    /// Number of valid entries in `change_logs`.
+    pub buffer_size: u64,
+    /// Circular changelog buffer of `max_buffer_size` entries.
+    pub change_logs: Vec<ChangeLog>,
+    pub rightmost_proof: MerkleTreePath,
+    /// Cached upper nodes of the tree, in heap order starting with the root's children.
+    pub canopy: Vec<[u8; 32]>,
+}
+
+impl ConcurrentMerkleTreeAccount {
+    pub fn from_account_data(account_data: &[u8]) -> Result<Self, BlockbusterError> {
+        let header = MerkleTreeHeader::from_account_data(account_data)?;
+        let mut data = &account_data[header.size()..];
+
+        let max_depth = header.max_depth as usize;
+        let max_buffer_size = header.max_buffer_size as usize;
+        // Checked up front so a corrupt header cannot trigger huge allocations.
+        let path_size = max_depth
+            .checked_add(1)
+            .and_then(|nodes| nodes.checked_mul(NODE_SIZE))
+            .and_then(|size| size.checked_add(8));
+        let body_size = path_size.and_then(|path_size| {
+            path_size
+                .checked_mul(max_buffer_size)?
+                .checked_add(path_size)?
+                .checked_add(24)
+        });
+        match body_size {
+            Some(body_size) if data.len() >= body_size => {}
+            _ => return Err(BlockbusterError::InvalidDataLength),
+        }
+
+        let sequence_number = u64::deserialize(&mut data)?;
+        let active_index = u64::deserialize(&mut data)?;
+        let buffer_size = u64::deserialize(&mut data)?;
+
+        let mut change_logs = Vec::with_capacity(max_buffer_size);
+        for _ in 0..max_buffer_size {
+            let root = <[u8; 32]>::deserialize(&mut data)?;
+            let path = read_nodes(&mut data, max_depth)?;
+            let index = u32::deserialize(&mut data)?;
+            let _padding = u32::deserialize(&mut data)?;
+            change_logs.push(ChangeLog { root, path, index });
+        }
+
+        let proof = read_nodes(&mut data, max_depth)?;
+        let leaf = <[u8; 32]>::deserialize(&mut data)?;
+        let index = u32::deserialize(&mut data)?;
+        let _padding = u32::deserialize(&mut data)?;
+        let rightmost_proof = MerkleTreePath { proof, leaf, index };
+
+        let canopy_len = data.len() / NODE_SIZE;
+        let canopy = read_nodes(&mut data, canopy_len)?;
+
+        Ok(ConcurrentMerkleTreeAccount {
+            header,
+            sequence_number,
+            active_index,
+            buffer_size,
+            change_logs,
+            rightmost_proof,
+            canopy,
+        })
+    }
+
+    /// The current root of the tree, from the most recent changelog.
+    pub fn root(&self) -> Option<[u8; 32]> {
+        self.change_logs
+            .get(self.active_index as usize)
+            .map(|change_log| change_log.root)
+    }
+}
+
+fn read_nodes(data: &mut &[u8], count: usize) -> Result<Vec<[u8; 32]>, BlockbusterError> {
+    (0..count)
+        .map(|_| <[u8; 32]>::deserialize(data).map_err(BlockbusterError::from))
+        .collect()
+}
+
+impl ParseResult for ConcurrentMerkleTreeAccount {
+    fn result(&self) -> &Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+    fn result_type(&self) -> ProgramParseResult {
+        ProgramParseResult::ConcurrentMerkleTree(self)
+    }
+}
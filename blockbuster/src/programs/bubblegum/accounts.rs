@@ -0,0 +1,376 @@
+//! Named accounts of each Bubblegum instruction, in the order the program expects them.
+
+use super::ID;
+
+instruction_accounts! {
+    program = ID;
+
+    pub struct CreateTreeAccounts {
+        tree_config: required,
+        merkle_tree: required,
+        payer: required,
+        tree_creator: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct SetTreeDelegateAccounts {
+        tree_config: required,
+        tree_creator: required,
+        new_tree_delegate: required,
+        merkle_tree: required,
+        system_program: required,
+    }
+
+    pub struct SetDecompressibleStateAccounts {
+        tree_config: required,
+        tree_creator: required,
+    }
+
+    pub struct MintV1Accounts {
+        tree_config: required,
+        leaf_owner: required,
+        leaf_delegate: required,
+        merkle_tree: required,
+        payer: required,
+        tree_creator_or_delegate: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    /// Shared by `MintToCollectionV1`, `VerifyCollection`, `UnverifyCollection` and
+    /// `SetAndVerifyCollection`.
+    pub struct CollectionAccounts {
+        tree_config: required,
+        leaf_owner: required,
+        leaf_delegate: required,
+        merkle_tree: required,
+        payer: required,
+        tree_creator_or_delegate: required,
+        collection_authority: required,
+        collection_authority_record_pda: optional,
+        collection_mint: required,
+        collection_metadata: required,
+        collection_edition: required,
+        bubblegum_signer: required,
+        log_wrapper: required,
+        compression_program: required,
+        token_metadata_program: required,
+        system_program: required,
+    }
+
+    /// Shared by `VerifyCreator` and `UnverifyCreator`.
+    pub struct CreatorVerificationAccounts {
+        tree_config: required,
+        leaf_owner: required,
+        leaf_delegate: required,
+        merkle_tree: required,
+        payer: required,
+        creator: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct UpdateMetadataAccounts {
+        tree_config: required,
+        authority: required,
+        collection_mint: optional,
+        collection_metadata: optional,
+        collection_authority_record_pda: optional,
+        leaf_owner: required,
+        leaf_delegate: required,
+        payer: required,
+        merkle_tree: required,
+        log_wrapper: required,
+        compression_program: required,
+        token_metadata_program: required,
+        system_program: required,
+    }
+
+    pub struct TransferAccounts {
+        tree_config: required,
+        leaf_owner: required,
+        leaf_delegate: required,
+        new_leaf_owner: required,
+        merkle_tree: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct DelegateAccounts {
+        tree_config: required,
+        leaf_owner: required,
+        previous_leaf_delegate: required,
+        new_leaf_delegate: required,
+        merkle_tree: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct BurnAccounts {
+        tree_config: required,
+        leaf_owner: required,
+        leaf_delegate: required,
+        merkle_tree: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct RedeemAccounts {
+        tree_config: required,
+        leaf_owner: required,
+        leaf_delegate: required,
+        merkle_tree: required,
+        voucher: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct CancelRedeemAccounts {
+        tree_config: required,
+        leaf_owner: required,
+        merkle_tree: required,
+        voucher: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct DecompressV1Accounts {
+        voucher: required,
+        leaf_owner: required,
+        token_account: required,
+        mint: required,
+        mint_authority: required,
+        metadata: required,
+        master_edition: required,
+        system_program: required,
+        sysvar_rent: required,
+        token_metadata_program: required,
+        token_program: required,
+        associated_token_program: required,
+        log_wrapper: required,
+    }
+
+    pub struct CompressAccounts {
+        tree_config: required,
+        leaf_owner: required,
+        leaf_delegate: required,
+        merkle_tree: required,
+        token_account: required,
+        mint: required,
+        metadata: required,
+        master_edition: required,
+        payer: required,
+        log_wrapper: required,
+        compression_program: required,
+        token_program: required,
+        token_metadata_program: required,
+        system_program: required,
+    }
+
+    pub struct CreateTreeV2Accounts {
+        tree_config: required,
+        merkle_tree: required,
+        payer: required,
+        tree_creator: optional,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct MintV2Accounts {
+        tree_config: required,
+        payer: required,
+        tree_creator_or_delegate: optional,
+        collection_authority: optional,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        merkle_tree: required,
+        core_collection: optional,
+        mpl_core_cpi_signer: optional,
+        log_wrapper: required,
+        compression_program: required,
+        mpl_core_program: required,
+        system_program: required,
+    }
+
+    pub struct TransferV2Accounts {
+        tree_config: required,
+        payer: required,
+        authority: optional,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        new_leaf_owner: required,
+        merkle_tree: required,
+        core_collection: optional,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct BurnV2Accounts {
+        tree_config: required,
+        payer: required,
+        authority: optional,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        merkle_tree: required,
+        core_collection: optional,
+        mpl_core_cpi_signer: required,
+        log_wrapper: required,
+        compression_program: required,
+        mpl_core_program: required,
+        system_program: required,
+    }
+
+    /// Shared by `DelegateV2` and `DelegateAndFreezeV2`.
+    pub struct DelegateV2Accounts {
+        tree_config: required,
+        payer: required,
+        leaf_owner: required,
+        previous_leaf_delegate: optional,
+        new_leaf_delegate: required,
+        merkle_tree: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    /// Shared by `FreezeV2` and `ThawV2`.
+    pub struct FreezeV2Accounts {
+        tree_config: required,
+        payer: required,
+        authority: optional,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        merkle_tree: required,
+        core_collection: optional,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct ThawAndRevokeV2Accounts {
+        tree_config: required,
+        payer: required,
+        leaf_delegate: required,
+        leaf_owner: required,
+        merkle_tree: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct SetNonTransferableV2Accounts {
+        tree_config: required,
+        payer: required,
+        authority: optional,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        merkle_tree: required,
+        core_collection: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    /// Shared by `VerifyCreatorV2` and `UnverifyCreatorV2`.
+    pub struct CreatorVerificationV2Accounts {
+        tree_config: required,
+        payer: required,
+        creator: required,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        merkle_tree: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct SetCollectionV2Accounts {
+        tree_config: required,
+        payer: required,
+        authority: optional,
+        new_collection_authority: optional,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        merkle_tree: required,
+        core_collection: optional,
+        new_core_collection: optional,
+        mpl_core_cpi_signer: required,
+        log_wrapper: required,
+        compression_program: required,
+        mpl_core_program: required,
+        system_program: required,
+    }
+
+    pub struct UpdateMetadataV2Accounts {
+        tree_config: required,
+        payer: required,
+        authority: optional,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        merkle_tree: required,
+        core_collection: optional,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct UpdateAssetDataV2Accounts {
+        tree_config: required,
+        payer: required,
+        authority: optional,
+        leaf_owner: required,
+        leaf_delegate: optional,
+        merkle_tree: required,
+        log_wrapper: required,
+        compression_program: required,
+        system_program: required,
+    }
+
+    pub struct CollectV2Accounts {
+        tree_config: required,
+        destination: required,
+    }
+}
+
+/// The named accounts of a parsed Bubblegum instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionAccounts {
+    CreateTree(CreateTreeAccounts),
+    SetTreeDelegate(SetTreeDelegateAccounts),
+    SetDecompressibleState(SetDecompressibleStateAccounts),
+    MintV1(MintV1Accounts),
+    Collection(CollectionAccounts),
+    CreatorVerification(CreatorVerificationAccounts),
+    UpdateMetadata(UpdateMetadataAccounts),
+    Transfer(TransferAccounts),
+    Delegate(DelegateAccounts),
+    Burn(BurnAccounts),
+    Redeem(RedeemAccounts),
+    CancelRedeem(CancelRedeemAccounts),
+    DecompressV1(DecompressV1Accounts),
+    Compress(CompressAccounts),
+    CreateTreeV2(CreateTreeV2Accounts),
+    MintV2(MintV2Accounts),
+    TransferV2(TransferV2Accounts),
+    BurnV2(BurnV2Accounts),
+    DelegateV2(DelegateV2Accounts),
+    FreezeV2(FreezeV2Accounts),
+    ThawAndRevokeV2(ThawAndRevokeV2Accounts),
+    SetNonTransferableV2(SetNonTransferableV2Accounts),
+    CreatorVerificationV2(CreatorVerificationV2Accounts),
+    SetCollectionV2(SetCollectionV2Accounts),
+    UpdateMetadataV2(UpdateMetadataV2Accounts),
+    UpdateAssetDataV2(UpdateAssetDataV2Accounts),
+    CollectV2(CollectV2Accounts),
+}
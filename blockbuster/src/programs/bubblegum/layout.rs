@@ -0,0 +1,31 @@
+//! Historical Bubblegum instruction layouts.
+//!
+//! Bubblegum deployments before public trees wrote `create_tree` without the `public` argument.
+//! The two encodings have different lengths, so the layout is picked from the instruction data.
+
+use crate::error::BlockbusterError;
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_bubblegum::instructions::CreateTreeConfigInstructionArgs;
+
+/// `create_tree` arguments before public trees were introduced.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LegacyCreateTreeInstructionArgs {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+/// Decodes `create_tree` arguments of either layout. Legacy arguments are reported with
+/// `public: None`.
+pub fn decode_create_tree_args(
+    ix_data: &[u8],
+) -> Result<CreateTreeConfigInstructionArgs, BlockbusterError> {
+    if ix_data.len() == std::mem::size_of::<LegacyCreateTreeInstructionArgs>() {
+        let args = LegacyCreateTreeInstructionArgs::try_from_slice(ix_data)?;
+        return Ok(CreateTreeConfigInstructionArgs {
+            max_depth: args.max_depth,
+            max_buffer_size: args.max_buffer_size,
+            public: None,
+        });
+    }
+    Ok(CreateTreeConfigInstructionArgs::try_from_slice(ix_data)?)
+}
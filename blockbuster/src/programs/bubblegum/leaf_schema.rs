@@ -0,0 +1,206 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_bubblegum::types::BubblegumEventType;
+use solana_sdk::{keccak, pubkey::Pubkey};
+
+/// Leaf flag set while the asset is frozen by its owner or delegate.
+pub const ASSET_LEVEL_FROZEN_FLAG: u8 = 1 << 0;
+/// Leaf flag set while the asset is frozen by the collection's permanent freeze delegate.
+pub const PERMANENT_LEVEL_FROZEN_FLAG: u8 = 1 << 1;
+/// Leaf flag set when the asset is soulbound and can no longer be transferred.
+pub const NON_TRANSFERABLE_FLAG: u8 = 1 << 2;
+
+/// Leaf schema version.
+///
+/// Borsh compatible with `mpl_bubblegum::types::Version`, extended with the V2 leaf.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Version {
+    V1,
+    V2,
+}
+
+impl Version {
+    pub const fn to_bytes(self) -> u8 {
+        match self {
+            Version::V1 => 1,
+            Version::V2 => 2,
+        }
+    }
+}
+
+/// Compressed leaf contents.
+///
+/// Borsh compatible with `mpl_bubblegum::types::LeafSchema`, extended with the V2 leaf that adds
+/// the collection hash, asset data hash and leaf flags.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub enum LeafSchema {
+    V1 {
+        id: Pubkey,
+        owner: Pubkey,
+        delegate: Pubkey,
+        nonce: u64,
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+    },
+    V2 {
+        id: Pubkey,
+        owner: Pubkey,
+        delegate: Pubkey,
+        nonce: u64,
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        collection_hash: [u8; 32],
+        asset_data_hash: [u8; 32],
+        flags: u8,
+    },
+}
+
+impl LeafSchema {
+    /// Keccak hash of the leaf, as stored in the merkle tree.
+    pub fn hash(&self) -> [u8; 32] {
+        match self {
+            LeafSchema::V1 {
+                id,
+                owner,
+                delegate,
+                nonce,
+                data_hash,
+                creator_hash,
+            } => keccak::hashv(&[
+                &[self.version().to_bytes()],
+                id.as_ref(),
+                owner.as_ref(),
+                delegate.as_ref(),
+                nonce.to_le_bytes().as_ref(),
+                data_hash.as_ref(),
+                creator_hash.as_ref(),
+            ])
+            .to_bytes(),
+            LeafSchema::V2 {
+                id,
+                owner,
+                delegate,
+                nonce,
+                data_hash,
+                creator_hash,
+                collection_hash,
+                asset_data_hash,
+                flags,
+            } => keccak::hashv(&[
+                &[self.version().to_bytes()],
+                id.as_ref(),
+                owner.as_ref(),
+                delegate.as_ref(),
+                nonce.to_le_bytes().as_ref(),
+                data_hash.as_ref(),
+                creator_hash.as_ref(),
+                collection_hash.as_ref(),
+                asset_data_hash.as_ref(),
+                &[*flags],
+            ])
+            .to_bytes(),
+        }
+    }
+
+    pub const fn version(&self) -> Version {
+        match self {
+            LeafSchema::V1 { .. } => Version::V1,
+            LeafSchema::V2 { .. } => Version::V2,
+        }
+    }
+
+    pub const fn id(&self) -> Pubkey {
+        match self {
+            LeafSchema::V1 { id, .. } | LeafSchema::V2 { id, .. } => *id,
+        }
+    }
+
+    pub const fn owner(&self) -> Pubkey {
+        match self {
+            LeafSchema::V1 { owner, .. } | LeafSchema::V2 { owner, .. } => *owner,
+        }
+    }
+
+    pub const fn delegate(&self) -> Pubkey {
+        match self {
+            LeafSchema::V1 { delegate, .. } | LeafSchema::V2 { delegate, .. } => *delegate,
+        }
+    }
+
+    pub const fn nonce(&self) -> u64 {
+        match self {
+            LeafSchema::V1 { nonce, .. } | LeafSchema::V2 { nonce, .. } => *nonce,
+        }
+    }
+
+    pub const fn data_hash(&self) -> [u8; 32] {
+        match self {
+            LeafSchema::V1 { data_hash, .. } | LeafSchema::V2 { data_hash, .. } => *data_hash,
+        }
+    }
+
+    pub const fn creator_hash(&self) -> [u8; 32] {
+        match self {
+            LeafSchema::V1 { creator_hash, .. } | LeafSchema::V2 { creator_hash, .. } => {
+                *creator_hash
+            }
+        }
+    }
+
+    /// Only V2 leaves carry a collection hash.
+    pub const fn collection_hash(&self) -> Option<[u8; 32]> {
+        match self {
+            LeafSchema::V1 { .. } => None,
+            LeafSchema::V2 {
+                collection_hash, ..
+            } => Some(*collection_hash),
+        }
+    }
+
+    /// Only V2 leaves carry an asset data hash.
+    pub const fn asset_data_hash(&self) -> Option<[u8; 32]> {
+        match self {
+            LeafSchema::V1 { .. } => None,
+            LeafSchema::V2 {
+                asset_data_hash, ..
+            } => Some(*asset_data_hash),
+        }
+    }
+
+    /// Leaf flags, always empty for V1 leaves.
+    pub const fn flags(&self) -> u8 {
+        match self {
+            LeafSchema::V1 { .. } => 0,
+            LeafSchema::V2 { flags, .. } => *flags,
+        }
+    }
+
+    pub const fn is_frozen(&self) -> bool {
+        self.flags() & (ASSET_LEVEL_FROZEN_FLAG | PERMANENT_LEVEL_FROZEN_FLAG) != 0
+    }
+
+    pub const fn is_non_transferable(&self) -> bool {
+        self.flags() & NON_TRANSFERABLE_FLAG != 0
+    }
+}
+
+/// Application data event emitted by Bubblegum through the noop program.
+///
+/// Borsh compatible with `mpl_bubblegum::LeafSchemaEvent`, using the versioned `LeafSchema` above.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LeafSchemaEvent {
+    pub event_type: BubblegumEventType,
+    pub version: Version,
+    pub schema: LeafSchema,
+    pub leaf_hash: [u8; 32],
+}
+
+impl LeafSchemaEvent {
+    pub const fn new(version: Version, schema: LeafSchema, leaf_hash: [u8; 32]) -> Self {
+        Self {
+            event_type: BubblegumEventType::LeafSchemaEvent,
+            version,
+            schema,
+            leaf_hash,
+        }
+    }
+}
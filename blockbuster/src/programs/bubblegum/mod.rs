@@ -1,29 +1,64 @@
 use crate::{
     error::BlockbusterError,
     instruction::InstructionBundle,
-    program_handler::{NotUsed, ParseResult, ProgramParser},
+    noop::NoopProgram,
+    program_handler::{ParseResult, ProgramParser},
     programs::ProgramParseResult,
 };
 use borsh::de::BorshDeserialize;
 use log::warn;
+pub use mpl_bubblegum::{
+    hash::{hash_creators, hash_metadata},
+    types::{DecompressibleState, UseMethod},
+    utils::get_asset_id,
+    ID,
+};
 use mpl_bubblegum::{
-    get_instruction_type,
     instructions::{
-        UnverifyCreatorInstructionArgs, UpdateMetadataInstructionArgs, VerifyCreatorInstructionArgs,
+        BurnInstructionArgs, DelegateInstructionArgs, RedeemInstructionArgs,
+        SetDecompressibleStateInstructionArgs, TransferInstructionArgs,
+        UnverifyCreatorInstructionArgs, UpdateMetadataInstructionArgs,
+        VerifyCreatorInstructionArgs,
     },
     types::{BubblegumEventType, MetadataArgs, UpdateArgs},
 };
-pub use mpl_bubblegum::{
-    types::{LeafSchema, UseMethod},
-    InstructionName, LeafSchemaEvent, ID,
-};
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
 use solana_sdk::pubkey::Pubkey;
 pub use spl_account_compression::events::{
     AccountCompressionEvent::{self, ApplicationData, ChangeLog},
     ApplicationDataEvent, ChangeLogEvent, ChangeLogEventV1,
 };
 
-use spl_noop;
+pub mod accounts;
+mod instruction_name;
+pub mod layout;
+mod leaf_schema;
+mod state;
+pub mod v2;
+mod verify;
+
+use accounts::{
+    BurnAccounts, BurnV2Accounts, CancelRedeemAccounts, CollectV2Accounts, CollectionAccounts,
+    CompressAccounts, CreateTreeAccounts, CreateTreeV2Accounts, CreatorVerificationAccounts,
+    CreatorVerificationV2Accounts, DecompressV1Accounts, DelegateAccounts, DelegateV2Accounts,
+    FreezeV2Accounts, InstructionAccounts, MintV1Accounts, MintV2Accounts, RedeemAccounts,
+    SetCollectionV2Accounts, SetDecompressibleStateAccounts, SetNonTransferableV2Accounts,
+    SetTreeDelegateAccounts, ThawAndRevokeV2Accounts, TransferAccounts, TransferV2Accounts,
+    UpdateAssetDataV2Accounts, UpdateMetadataAccounts, UpdateMetadataV2Accounts,
+};
+pub use instruction_name::{get_instruction_type, InstructionName};
+use layout::decode_create_tree_args;
+pub use leaf_schema::{
+    LeafSchema, LeafSchemaEvent, Version, ASSET_LEVEL_FROZEN_FLAG, NON_TRANSFERABLE_FLAG,
+    PERMANENT_LEVEL_FROZEN_FLAG,
+};
+pub use state::{BubblegumAccount, TreeConfig, Voucher};
+use v2::{
+    AssetDataSchema, CollectionLeafInstructionV2Args, CreateTreeV2InstructionArgs,
+    CreatorVerificationV2InstructionArgs, LeafInstructionV2Args, MetadataArgsV2,
+    MintV2InstructionArgs, SetCollectionV2InstructionArgs, UpdateAssetDataV2InstructionArgs,
+    UpdateMetadataV2InstructionArgs,
+};
 
 #[derive(Eq, PartialEq)]
 pub enum Payload {
@@ -35,10 +70,30 @@ pub enum Payload {
     },
     Decompress {
         args: MetadataArgs,
+        voucher: Pubkey,
+        leaf_owner: Pubkey,
+        token_account: Pubkey,
+        /// The mint of a decompressed asset is its asset ID.
+        mint: Pubkey,
+        /// Token Metadata PDA derived from `mint`.
+        metadata: Pubkey,
+        /// Token Metadata master edition PDA derived from `mint`.
+        master_edition: Pubkey,
     },
     CancelRedeem {
         root: Pubkey,
     },
+    Compress {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        token_account: Pubkey,
+        mint: Pubkey,
+        metadata: Pubkey,
+        master_edition: Pubkey,
+        /// The compressed leaf appended to the tree, when its leaf event was emitted.
+        leaf: Option<LeafSchema>,
+    },
     CreatorVerification {
         metadata: MetadataArgs,
         creator: Pubkey,
@@ -51,24 +106,291 @@ pub enum Payload {
     UpdateMetadata {
         current_metadata: MetadataArgs,
         update_args: UpdateArgs,
+        /// `current_metadata` with `update_args` applied, as written to the leaf.
+        updated_metadata: MetadataArgs,
+        tree_id: Pubkey,
+    },
+    Transfer {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        new_leaf_owner: Pubkey,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+    },
+    Delegate {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        previous_leaf_delegate: Pubkey,
+        new_leaf_delegate: Pubkey,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+    },
+    Burn {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+    },
+    Redeem {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        voucher: Pubkey,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+    },
+    CreateTree {
+        tree_id: Pubkey,
+        tree_creator: Pubkey,
+        payer: Pubkey,
+        max_depth: u32,
+        max_buffer_size: u32,
+        public: Option<bool>,
+    },
+    SetTreeDelegate {
+        tree_id: Pubkey,
+        tree_creator: Pubkey,
+        new_tree_delegate: Pubkey,
+    },
+    SetDecompressibleState {
+        tree_config: Pubkey,
+        tree_creator: Pubkey,
+        decompressable_state: DecompressibleState,
+    },
+    MintV2 {
+        args: MetadataArgsV2,
+        asset_data: Option<Vec<u8>>,
+        asset_data_schema: Option<AssetDataSchema>,
+        authority: Pubkey,
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+    },
+    TransferV2 {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        new_leaf_owner: Pubkey,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        asset_data_hash: Option<[u8; 32]>,
+        flags: Option<u8>,
+        nonce: u64,
+        index: u32,
+    },
+    BurnV2 {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        asset_data_hash: Option<[u8; 32]>,
+        flags: Option<u8>,
+        nonce: u64,
+        index: u32,
+    },
+    /// Produced by both `DelegateV2` and `DelegateAndFreezeV2`.
+    DelegateV2 {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        previous_leaf_delegate: Pubkey,
+        new_leaf_delegate: Pubkey,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        collection_hash: Option<[u8; 32]>,
+        asset_data_hash: Option<[u8; 32]>,
+        flags: Option<u8>,
+        nonce: u64,
+        index: u32,
+    },
+    /// Produced by `FreezeV2` (`freeze == true`), `ThawV2` and `ThawAndRevokeV2`.
+    FreezeV2 {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        freeze: bool,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        collection_hash: Option<[u8; 32]>,
+        asset_data_hash: Option<[u8; 32]>,
+        flags: Option<u8>,
+        nonce: u64,
+        index: u32,
+    },
+    SetNonTransferableV2 {
+        tree_id: Pubkey,
+        leaf_owner: Pubkey,
+        leaf_delegate: Pubkey,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        asset_data_hash: Option<[u8; 32]>,
+        flags: Option<u8>,
+        nonce: u64,
+        index: u32,
+    },
+    CreatorVerificationV2 {
+        metadata: MetadataArgsV2,
+        creator: Pubkey,
+        verify: bool,
+    },
+    SetCollectionV2 {
+        metadata: MetadataArgsV2,
+        new_collection: Option<Pubkey>,
+        tree_id: Pubkey,
+    },
+    UpdateMetadataV2 {
+        current_metadata: MetadataArgsV2,
+        update_args: UpdateArgs,
+        /// `current_metadata` with `update_args` applied, as written to the leaf.
+        updated_metadata: MetadataArgsV2,
+        tree_id: Pubkey,
+    },
+    UpdateAssetDataV2 {
+        previous_asset_data: Option<Vec<u8>>,
+        new_asset_data: Option<Vec<u8>>,
+        new_asset_data_schema: Option<AssetDataSchema>,
         tree_id: Pubkey,
     },
 }
-//TODO add more of the parsing here to minimize program transformer code
+
+/// A noop event tagged with the position of the noop instruction in `InstructionBundle::inner_ix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedEvent<T> {
+    pub inner_ix_index: usize,
+    /// The noop program that logged the event.
+    pub noop_program: NoopProgram,
+    pub event: T,
+}
+
+/// A noop event that could not be used, reported by
+/// `BubblegumParser::handle_instruction_with_diagnostics` and
+/// `BubblegumInstruction::leaf_hash_mismatches`.
+#[derive(Debug)]
+pub struct NoopEventDiagnostic {
+    pub inner_ix_index: usize,
+    pub error: BlockbusterError,
+}
+
 pub struct BubblegumInstruction {
     pub instruction: InstructionName,
+    /// The last changelog emitted by the instruction, see `tree_updates` for all of them.
     pub tree_update: Option<ChangeLogEventV1>,
+    /// The last leaf schema event emitted by the instruction, see `leaf_updates` for all of them.
     pub leaf_update: Option<LeafSchemaEvent>,
+    /// Every changelog emitted by the instruction, in inner instruction order.
+    pub tree_updates: Vec<IndexedEvent<ChangeLogEventV1>>,
+    /// Every leaf schema event emitted by the instruction, in inner instruction order.
+    pub leaf_updates: Vec<IndexedEvent<LeafSchemaEvent>>,
     pub payload: Option<Payload>,
+    /// The named accounts of the instruction.
+    pub accounts: Option<InstructionAccounts>,
+    /// Asset ID derived from the tree and the leaf nonce, when the instruction identifies a leaf.
+    pub asset_id: Option<Pubkey>,
+    /// Keccak hash of `leaf_update`'s schema.
+    pub leaf_hash: Option<[u8; 32]>,
 }
 
 impl BubblegumInstruction {
-    pub fn new(ix: InstructionName) -> Self {
+    pub const fn new(ix: InstructionName) -> Self {
         BubblegumInstruction {
             instruction: ix,
             tree_update: None,
             leaf_update: None,
+            tree_updates: Vec::new(),
+            leaf_updates: Vec::new(),
             payload: None,
+            accounts: None,
+            asset_id: None,
+            leaf_hash: None,
+        }
+    }
+
+    // The tree comes from the changelog or the accounts, the nonce from the leaf or the args.
+    fn tree_and_nonce(&self) -> Option<(Pubkey, u64)> {
+        let payload_tree_and_nonce = match &self.payload {
+            Some(Payload::Transfer { tree_id, nonce, .. })
+            | Some(Payload::Delegate { tree_id, nonce, .. })
+            | Some(Payload::Burn { tree_id, nonce, .. })
+            | Some(Payload::Redeem { tree_id, nonce, .. })
+            | Some(Payload::TransferV2 { tree_id, nonce, .. })
+            | Some(Payload::BurnV2 { tree_id, nonce, .. })
+            | Some(Payload::DelegateV2 { tree_id, nonce, .. })
+            | Some(Payload::FreezeV2 { tree_id, nonce, .. })
+            | Some(Payload::SetNonTransferableV2 { tree_id, nonce, .. }) => {
+                Some((*tree_id, *nonce))
+            }
+            _ => None,
+        };
+
+        match (&self.leaf_update, &self.tree_update) {
+            (Some(leaf), Some(changelog)) => Some((changelog.id, leaf.schema.nonce())),
+            _ => payload_tree_and_nonce,
+        }
+    }
+
+    /// Leaf events whose hash differs from the leaf of the changelog that follows them, each
+    /// reported as a `LeafHashMismatch` error.
+    ///
+    /// Mismatches do not fail `handle_instruction`, see `verify_leaf_hashes`.
+    pub fn leaf_hash_mismatches(&self) -> Vec<NoopEventDiagnostic> {
+        // Each leaf event is followed by the changelog of the tree modification that wrote the
+        // leaf.
+        let mut mismatches = Vec::new();
+        for leaf in self.leaf_updates.iter() {
+            let changelog = self
+                .tree_updates
+                .iter()
+                .find(|changelog| changelog.inner_ix_index > leaf.inner_ix_index);
+
+            if let Some(IndexedEvent {
+                event: changelog, ..
+            }) = changelog
+            {
+                let computed = leaf.event.schema.hash();
+                if let Some(node) = changelog.path.first() {
+                    if node.node != computed {
+                        mismatches.push(NoopEventDiagnostic {
+                            inner_ix_index: leaf.inner_ix_index,
+                            error: BlockbusterError::LeafHashMismatch {
+                                tree: changelog.id,
+                                seq: changelog.seq,
+                                computed,
+                                changelog: node.node,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+
+        mismatches
+    }
+
+    /// Fails with the first `LeafHashMismatch`, for consumers that reject instructions whose leaf
+    /// events disagree with the tree.
+    pub fn verify_leaf_hashes(&self) -> Result<(), BlockbusterError> {
+        match self.leaf_hash_mismatches().into_iter().next() {
+            Some(mismatch) => Err(mismatch.error),
+            None => Ok(()),
         }
     }
 }
@@ -96,7 +418,7 @@ impl ProgramParser for BubblegumParser {
         key == &ID
     }
     fn handles_account_updates(&self) -> bool {
-        false
+        true
     }
 
     fn handles_instructions(&self) -> bool {
@@ -104,15 +426,40 @@ impl ProgramParser for BubblegumParser {
     }
     fn handle_account(
         &self,
-        _account_data: &[u8],
+        account_data: &[u8],
     ) -> Result<Box<(dyn ParseResult + 'static)>, BlockbusterError> {
-        Ok(Box::new(NotUsed::new()))
+        Ok(Box::new(BubblegumAccount::from_account_data(account_data)?))
     }
 
     fn handle_instruction(
         &self,
         bundle: &InstructionBundle,
     ) -> Result<Box<(dyn ParseResult + 'static)>, BlockbusterError> {
+        Ok(Box::new(self.parse_instruction(bundle, None)?))
+    }
+}
+
+impl BubblegumParser {
+    /// Partial-result alternative to `handle_instruction`.
+    ///
+    /// Noop events that fail to decode, and leaf events that do not match their changelog, are
+    /// reported as diagnostics instead of failing the instruction, so the payload and every other
+    /// event are still returned.
+    pub fn handle_instruction_with_diagnostics(
+        &self,
+        bundle: &InstructionBundle,
+    ) -> Result<(BubblegumInstruction, Vec<NoopEventDiagnostic>), BlockbusterError> {
+        let mut diagnostics = Vec::new();
+        let b_inst = self.parse_instruction(bundle, Some(&mut diagnostics))?;
+        Ok((b_inst, diagnostics))
+    }
+
+    // Event errors are collected into `diagnostics` when present, otherwise they are returned.
+    fn parse_instruction(
+        &self,
+        bundle: &InstructionBundle,
+        mut diagnostics: Option<&mut Vec<NoopEventDiagnostic>>,
+    ) -> Result<BubblegumInstruction, BlockbusterError> {
         let InstructionBundle {
             txn_id,
             instruction,
@@ -127,32 +474,42 @@ impl ProgramParser for BubblegumParser {
         let ix_type = get_instruction_type(outer_ix_data);
         let mut b_inst = BubblegumInstruction::new(ix_type);
         if let Some(ixs) = inner_ix {
-            for (pid, cix) in ixs.iter() {
-                if pid == &spl_noop::id() && !cix.data.is_empty() {
+            for (inner_ix_index, (pid, cix)) in ixs.iter().enumerate() {
+                let Some(noop_program) = NoopProgram::from_program_id(pid) else {
+                    continue;
+                };
+                if !cix.data.is_empty() {
                     match AccountCompressionEvent::try_from_slice(&cix.data) {
                         Ok(result) => match result {
                             ChangeLog(changelog_event) => {
                                 let ChangeLogEvent::V1(changelog_event) = changelog_event;
-                                b_inst.tree_update = Some(changelog_event);
+                                b_inst.tree_update = Some(clone_changelog(&changelog_event));
+                                b_inst.tree_updates.push(IndexedEvent {
+                                    inner_ix_index,
+                                    noop_program,
+                                    event: changelog_event,
+                                });
                             }
                             ApplicationData(app_data) => {
                                 let ApplicationDataEvent::V1(app_data) = app_data;
-                                let app_data = app_data.application_data;
-
-                                let event_type_byte = if !app_data.is_empty() {
-                                    &app_data[0..1]
-                                } else {
-                                    return Err(BlockbusterError::DeserializationError);
-                                };
-
-                                match BubblegumEventType::try_from_slice(event_type_byte)? {
-                                    BubblegumEventType::Uninitialized => {
-                                        return Err(BlockbusterError::MissingBubblegumEventData);
-                                    }
-                                    BubblegumEventType::LeafSchemaEvent => {
-                                        b_inst.leaf_update =
-                                            Some(LeafSchemaEvent::try_from_slice(&app_data)?);
+                                match parse_leaf_schema_event(&app_data.application_data) {
+                                    Ok(leaf_event) => {
+                                        b_inst.leaf_update = Some(leaf_event.clone());
+                                        b_inst.leaf_updates.push(IndexedEvent {
+                                            inner_ix_index,
+                                            noop_program,
+                                            event: leaf_event,
+                                        });
                                     }
+                                    Err(error) => match diagnostics.as_deref_mut() {
+                                        Some(diagnostics) => {
+                                            diagnostics.push(NoopEventDiagnostic {
+                                                inner_ix_index,
+                                                error,
+                                            });
+                                        }
+                                        None => return Err(error),
+                                    },
                                 }
                             }
                         },
@@ -161,6 +518,14 @@ impl ProgramParser for BubblegumParser {
                                 "Error while deserializing txn {:?} with noop data: {:?}",
                                 txn_id, e
                             );
+                            if let Some(diagnostics) = diagnostics.as_deref_mut() {
+                                diagnostics.push(NoopEventDiagnostic {
+                                    inner_ix_index,
+                                    error: BlockbusterError::CustomDeserializationError(
+                                        e.to_string(),
+                                    ),
+                                });
+                            }
                         }
                     }
                 }
@@ -169,56 +534,239 @@ impl ProgramParser for BubblegumParser {
 
         if outer_ix_data.len() >= 8 {
             let ix_data = &outer_ix_data[8..];
-            if !ix_data.is_empty() {
-                match b_inst.instruction {
-                    InstructionName::MintV1 => {
-                        b_inst.payload = Some(build_mint_v1_payload(keys, ix_data, false)?);
-                    }
+            match b_inst.instruction {
+                InstructionName::MintV1 => {
+                    let accounts = MintV1Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_mint_v1_payload(
+                        accounts.tree_config,
+                        accounts.merkle_tree,
+                        ix_data,
+                        false,
+                    )?);
+                    b_inst.accounts = Some(InstructionAccounts::MintV1(accounts));
+                }
+                InstructionName::MintToCollectionV1 => {
+                    let accounts = CollectionAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_mint_v1_payload(
+                        accounts.tree_config,
+                        accounts.merkle_tree,
+                        ix_data,
+                        true,
+                    )?);
+                    b_inst.accounts = Some(InstructionAccounts::Collection(accounts));
+                }
+                InstructionName::DecompressV1 => {
+                    let accounts = DecompressV1Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_decompress_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::DecompressV1(accounts));
+                }
+                InstructionName::CancelRedeem => {
+                    let accounts = CancelRedeemAccounts::try_from_keys(keys)?;
+                    let slice: [u8; 32] = ix_data
+                        .try_into()
+                        .map_err(|_e| BlockbusterError::InstructionParsingError)?;
+                    let root = Pubkey::new_from_array(slice);
+                    b_inst.payload = Some(Payload::CancelRedeem { root });
+                    b_inst.accounts = Some(InstructionAccounts::CancelRedeem(accounts));
+                }
+                InstructionName::Compress => {
+                    let accounts = CompressAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_compress_payload(
+                        &accounts,
+                        b_inst.leaf_update.as_ref(),
+                    ));
+                    b_inst.accounts = Some(InstructionAccounts::Compress(accounts));
+                }
+                InstructionName::VerifyCreator => {
+                    let accounts = CreatorVerificationAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_creator_verification_payload(
+                        &accounts, ix_data, true,
+                    )?);
+                    b_inst.accounts = Some(InstructionAccounts::CreatorVerification(accounts));
+                }
+                InstructionName::UnverifyCreator => {
+                    let accounts = CreatorVerificationAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_creator_verification_payload(
+                        &accounts, ix_data, false,
+                    )?);
+                    b_inst.accounts = Some(InstructionAccounts::CreatorVerification(accounts));
+                }
+                InstructionName::VerifyCollection | InstructionName::SetAndVerifyCollection => {
+                    let accounts = CollectionAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_collection_verification_payload(&accounts, true));
+                    b_inst.accounts = Some(InstructionAccounts::Collection(accounts));
+                }
+                InstructionName::UnverifyCollection => {
+                    let accounts = CollectionAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_collection_verification_payload(&accounts, false));
+                    b_inst.accounts = Some(InstructionAccounts::Collection(accounts));
+                }
+                InstructionName::UpdateMetadata => {
+                    let accounts = UpdateMetadataAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_update_metadata_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::UpdateMetadata(accounts));
+                }
+                InstructionName::Transfer => {
+                    let accounts = TransferAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_transfer_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::Transfer(accounts));
+                }
+                InstructionName::Delegate => {
+                    let accounts = DelegateAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_delegate_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::Delegate(accounts));
+                }
+                InstructionName::Burn => {
+                    let accounts = BurnAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_burn_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::Burn(accounts));
+                }
+                InstructionName::Redeem => {
+                    let accounts = RedeemAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_redeem_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::Redeem(accounts));
+                }
+                InstructionName::CreateTree => {
+                    let accounts = CreateTreeAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_create_tree_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::CreateTree(accounts));
+                }
+                InstructionName::SetTreeDelegate => {
+                    let accounts = SetTreeDelegateAccounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_set_tree_delegate_payload(&accounts));
+                    b_inst.accounts = Some(InstructionAccounts::SetTreeDelegate(accounts));
+                }
+                InstructionName::SetDecompressibleState => {
+                    let accounts = SetDecompressibleStateAccounts::try_from_keys(keys)?;
+                    b_inst.payload =
+                        Some(build_set_decompressible_state_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::SetDecompressibleState(accounts));
+                }
+                InstructionName::CreateTreeV2 => {
+                    let accounts = CreateTreeV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_create_tree_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::CreateTreeV2(accounts));
+                }
+                InstructionName::MintV2 => {
+                    let accounts = MintV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_mint_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::MintV2(accounts));
+                }
+                InstructionName::TransferV2 => {
+                    let accounts = TransferV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_transfer_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::TransferV2(accounts));
+                }
+                InstructionName::BurnV2 => {
+                    let accounts = BurnV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_burn_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::BurnV2(accounts));
+                }
+                InstructionName::DelegateV2 | InstructionName::DelegateAndFreezeV2 => {
+                    let accounts = DelegateV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_delegate_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::DelegateV2(accounts));
+                }
+                InstructionName::FreezeV2 => {
+                    let accounts = FreezeV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_freeze_v2_payload(&accounts, ix_data, true)?);
+                    b_inst.accounts = Some(InstructionAccounts::FreezeV2(accounts));
+                }
+                InstructionName::ThawV2 => {
+                    let accounts = FreezeV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_freeze_v2_payload(&accounts, ix_data, false)?);
+                    b_inst.accounts = Some(InstructionAccounts::FreezeV2(accounts));
+                }
+                InstructionName::ThawAndRevokeV2 => {
+                    let accounts = ThawAndRevokeV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_thaw_and_revoke_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::ThawAndRevokeV2(accounts));
+                }
+                InstructionName::SetNonTransferableV2 => {
+                    let accounts = SetNonTransferableV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload =
+                        Some(build_set_non_transferable_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::SetNonTransferableV2(accounts));
+                }
+                InstructionName::VerifyCreatorV2 => {
+                    let accounts = CreatorVerificationV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_creator_verification_v2_payload(
+                        &accounts, ix_data, true,
+                    )?);
+                    b_inst.accounts = Some(InstructionAccounts::CreatorVerificationV2(accounts));
+                }
+                InstructionName::UnverifyCreatorV2 => {
+                    let accounts = CreatorVerificationV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_creator_verification_v2_payload(
+                        &accounts, ix_data, false,
+                    )?);
+                    b_inst.accounts = Some(InstructionAccounts::CreatorVerificationV2(accounts));
+                }
+                InstructionName::SetCollectionV2 => {
+                    let accounts = SetCollectionV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_set_collection_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::SetCollectionV2(accounts));
+                }
+                InstructionName::UpdateMetadataV2 => {
+                    let accounts = UpdateMetadataV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_update_metadata_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::UpdateMetadataV2(accounts));
+                }
+                InstructionName::UpdateAssetDataV2 => {
+                    let accounts = UpdateAssetDataV2Accounts::try_from_keys(keys)?;
+                    b_inst.payload = Some(build_update_asset_data_v2_payload(&accounts, ix_data)?);
+                    b_inst.accounts = Some(InstructionAccounts::UpdateAssetDataV2(accounts));
+                }
+                InstructionName::CollectV2 => {
+                    let accounts = CollectV2Accounts::try_from_keys(keys)?;
+                    b_inst.accounts = Some(InstructionAccounts::CollectV2(accounts));
+                }
+                InstructionName::Unknown => {}
+            };
+        }
 
-                    InstructionName::MintToCollectionV1 => {
-                        b_inst.payload = Some(build_mint_v1_payload(keys, ix_data, true)?);
-                    }
-                    InstructionName::DecompressV1 => {
-                        let args: MetadataArgs = MetadataArgs::try_from_slice(ix_data)?;
-                        b_inst.payload = Some(Payload::Decompress { args });
-                    }
-                    InstructionName::CancelRedeem => {
-                        let slice: [u8; 32] = ix_data
-                            .try_into()
-                            .map_err(|_e| BlockbusterError::InstructionParsingError)?;
-                        let root = Pubkey::new_from_array(slice);
-                        b_inst.payload = Some(Payload::CancelRedeem { root });
-                    }
-                    InstructionName::VerifyCreator => {
-                        b_inst.payload =
-                            Some(build_creator_verification_payload(keys, ix_data, true)?);
-                    }
-                    InstructionName::UnverifyCreator => {
-                        b_inst.payload =
-                            Some(build_creator_verification_payload(keys, ix_data, false)?);
-                    }
-                    InstructionName::VerifyCollection | InstructionName::SetAndVerifyCollection => {
-                        b_inst.payload = Some(build_collection_verification_payload(keys, true)?);
-                    }
-                    InstructionName::UnverifyCollection => {
-                        b_inst.payload = Some(build_collection_verification_payload(keys, false)?);
-                    }
-                    InstructionName::UpdateMetadata => {
-                        b_inst.payload = Some(build_update_metadata_payload(keys, ix_data)?);
-                    }
-                    _ => {}
-                };
-            }
+        if let Some(diagnostics) = diagnostics {
+            diagnostics.extend(b_inst.leaf_hash_mismatches());
         }
+        b_inst.leaf_hash = b_inst.leaf_update.as_ref().map(|leaf| leaf.schema.hash());
+        b_inst.asset_id = match &b_inst.payload {
+            Some(Payload::Decompress { mint, .. }) => Some(*mint),
+            _ => b_inst
+                .tree_and_nonce()
+                .map(|(tree, nonce)| get_asset_id(&tree, nonce)),
+        };
 
-        Ok(Box::new(b_inst))
+        Ok(b_inst)
+    }
+}
+
+fn parse_leaf_schema_event(app_data: &[u8]) -> Result<LeafSchemaEvent, BlockbusterError> {
+    let event_type_byte = if !app_data.is_empty() {
+        &app_data[0..1]
+    } else {
+        return Err(BlockbusterError::DeserializationError);
+    };
+
+    match BubblegumEventType::try_from_slice(event_type_byte)? {
+        BubblegumEventType::Uninitialized => Err(BlockbusterError::MissingBubblegumEventData),
+        BubblegumEventType::LeafSchemaEvent => Ok(LeafSchemaEvent::try_from_slice(app_data)?),
+    }
+}
+
+// `ChangeLogEventV1` does not implement `Clone`.
+fn clone_changelog(changelog: &ChangeLogEventV1) -> ChangeLogEventV1 {
+    ChangeLogEventV1 {
+        id: changelog.id,
+        path: changelog.path.clone(),
+        seq: changelog.seq,
+        index: changelog.index,
     }
 }
 
 // See Bubblegum documentation for offsets and positions:
 // https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-verify_creator-and-unverify_creator
 fn build_creator_verification_payload(
-    keys: &[Pubkey],
+    accounts: &CreatorVerificationAccounts,
     ix_data: &[u8],
     verify: bool,
 ) -> Result<Payload, BlockbusterError> {
@@ -228,13 +776,9 @@ fn build_creator_verification_payload(
         UnverifyCreatorInstructionArgs::try_from_slice(ix_data)?.metadata
     };
 
-    let creator = *keys
-        .get(5)
-        .ok_or(BlockbusterError::InstructionParsingError)?;
-
     Ok(Payload::CreatorVerification {
         metadata,
-        creator,
+        creator: accounts.creator,
         verify,
     })
 }
@@ -242,60 +786,501 @@ fn build_creator_verification_payload(
 // See Bubblegum for offsets and positions:
 // https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-verify_collection-unverify_collection-and-set_and_verify_collection
 // This uses the account.  The collection is only provided as an argument for `set_and_verify_collection`.
-fn build_collection_verification_payload(
-    keys: &[Pubkey],
+const fn build_collection_verification_payload(
+    accounts: &CollectionAccounts,
     verify: bool,
-) -> Result<Payload, BlockbusterError> {
-    let collection = *keys
-        .get(8)
-        .ok_or(BlockbusterError::InstructionParsingError)?;
-    Ok(Payload::CollectionVerification { collection, verify })
+) -> Payload {
+    Payload::CollectionVerification {
+        collection: accounts.collection_mint,
+        verify,
+    }
 }
 
 // See Bubblegum for offsets and positions:
 // https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md
 fn build_mint_v1_payload(
-    keys: &[Pubkey],
+    tree_config: Pubkey,
+    merkle_tree: Pubkey,
     ix_data: &[u8],
     set_verify: bool,
 ) -> Result<Payload, BlockbusterError> {
-    let mut args: MetadataArgs = MetadataArgs::try_from_slice(ix_data)?;
+    let mut args = MetadataArgs::try_from_slice(ix_data)?;
     if set_verify {
         if let Some(ref mut col) = args.collection {
             col.verified = true;
         }
     }
 
-    let authority = *keys
-        .first()
-        .ok_or(BlockbusterError::InstructionParsingError)?;
+    Ok(Payload::MintV1 {
+        args,
+        authority: tree_config,
+        tree_id: merkle_tree,
+    })
+}
 
-    let tree_id = *keys
-        .get(3)
-        .ok_or(BlockbusterError::InstructionParsingError)?;
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-decompress_v1
+// The metadata and master edition accounts are created by this instruction, so they are derived
+// from the mint rather than trusted from the account list.
+fn build_decompress_payload(
+    accounts: &DecompressV1Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = MetadataArgs::try_from_slice(ix_data)?;
 
-    Ok(Payload::MintV1 {
+    Ok(Payload::Decompress {
         args,
-        authority,
-        tree_id,
+        voucher: accounts.voucher,
+        leaf_owner: accounts.leaf_owner,
+        token_account: accounts.token_account,
+        mint: accounts.mint,
+        metadata: Metadata::find_pda(&accounts.mint).0,
+        master_edition: MasterEdition::find_pda(&accounts.mint).0,
     })
 }
 
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-compress
+// The instruction has no arguments, the leaf is read from the emitted leaf event.
+fn build_compress_payload(accounts: &CompressAccounts, leaf: Option<&LeafSchemaEvent>) -> Payload {
+    Payload::Compress {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        leaf_delegate: accounts.leaf_delegate,
+        token_account: accounts.token_account,
+        mint: accounts.mint,
+        metadata: accounts.metadata,
+        master_edition: accounts.master_edition,
+        leaf: leaf.map(|leaf| leaf.schema.clone()),
+    }
+}
+
 // See Bubblegum for offsets and positions:
 // https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md
 fn build_update_metadata_payload(
-    keys: &[Pubkey],
+    accounts: &UpdateMetadataAccounts,
     ix_data: &[u8],
 ) -> Result<Payload, BlockbusterError> {
     let args = UpdateMetadataInstructionArgs::try_from_slice(ix_data)?;
 
-    let tree_id = *keys
-        .get(8)
-        .ok_or(BlockbusterError::InstructionParsingError)?;
+    let updated_metadata = apply_update_args(&args.current_metadata, &args.update_args);
 
     Ok(Payload::UpdateMetadata {
         current_metadata: args.current_metadata,
         update_args: args.update_args,
+        updated_metadata,
+        tree_id: accounts.merkle_tree,
+    })
+}
+
+// `MetadataArgs` and `MetadataArgsV2` share the updatable fields.
+macro_rules! apply_update_args {
+    ($current:expr, $update_args:expr) => {{
+        let (current, update_args) = ($current, $update_args);
+        let mut updated = current.clone();
+        if let Some(name) = &update_args.name {
+            updated.name = name.clone();
+        }
+        if let Some(symbol) = &update_args.symbol {
+            updated.symbol = symbol.clone();
+        }
+        if let Some(uri) = &update_args.uri {
+            updated.uri = uri.clone();
+        }
+        if let Some(creators) = &update_args.creators {
+            updated.creators = creators.clone();
+        }
+        if let Some(seller_fee_basis_points) = update_args.seller_fee_basis_points {
+            updated.seller_fee_basis_points = seller_fee_basis_points;
+        }
+        if let Some(primary_sale_happened) = update_args.primary_sale_happened {
+            // Primary sale can only be flipped from false to true.
+            if !updated.primary_sale_happened {
+                updated.primary_sale_happened = primary_sale_happened;
+            }
+        }
+        if let Some(is_mutable) = update_args.is_mutable {
+            updated.is_mutable = is_mutable;
+        }
+        updated
+    }};
+}
+
+/// Applies `update_args` to `current` following the rules of Bubblegum's `update_metadata`
+/// instruction.
+///
+/// Creators are taken as given, including their `verified` flags: the program rejects updates
+/// that would verify a new creator or unverify an existing one, so any accepted update already
+/// carries the correct flags.
+pub fn apply_update_args(current: &MetadataArgs, update_args: &UpdateArgs) -> MetadataArgs {
+    apply_update_args!(current, update_args)
+}
+
+/// Applies `update_args` to `current` following the rules of Bubblegum's `update_metadata_v2`
+/// instruction, which are the same as the V1 rules.
+pub fn apply_update_args_v2(current: &MetadataArgsV2, update_args: &UpdateArgs) -> MetadataArgsV2 {
+    apply_update_args!(current, update_args)
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-transfer
+fn build_transfer_payload(
+    accounts: &TransferAccounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = TransferInstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::Transfer {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        leaf_delegate: accounts.leaf_delegate,
+        new_leaf_owner: accounts.new_leaf_owner,
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+    })
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-delegate
+fn build_delegate_payload(
+    accounts: &DelegateAccounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = DelegateInstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::Delegate {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        previous_leaf_delegate: accounts.previous_leaf_delegate,
+        new_leaf_delegate: accounts.new_leaf_delegate,
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+    })
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-burn
+fn build_burn_payload(
+    accounts: &BurnAccounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = BurnInstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::Burn {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        leaf_delegate: accounts.leaf_delegate,
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+    })
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-redeem-and-cancel_redeem
+fn build_redeem_payload(
+    accounts: &RedeemAccounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = RedeemInstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::Redeem {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        leaf_delegate: accounts.leaf_delegate,
+        voucher: accounts.voucher,
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+    })
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-create_tree
+fn build_create_tree_payload(
+    accounts: &CreateTreeAccounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = decode_create_tree_args(ix_data)?;
+
+    Ok(Payload::CreateTree {
+        tree_id: accounts.merkle_tree,
+        tree_creator: accounts.tree_creator,
+        payer: accounts.payer,
+        max_depth: args.max_depth,
+        max_buffer_size: args.max_buffer_size,
+        public: args.public,
+    })
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-set_tree_delegate
+const fn build_set_tree_delegate_payload(accounts: &SetTreeDelegateAccounts) -> Payload {
+    Payload::SetTreeDelegate {
+        tree_id: accounts.merkle_tree,
+        tree_creator: accounts.tree_creator,
+        new_tree_delegate: accounts.new_tree_delegate,
+    }
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md
+// The merkle tree is not an account of this instruction, so only the tree config is available.
+fn build_set_decompressible_state_payload(
+    accounts: &SetDecompressibleStateAccounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = SetDecompressibleStateInstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::SetDecompressibleState {
+        tree_config: accounts.tree_config,
+        tree_creator: accounts.tree_creator,
+        decompressable_state: args.decompressable_state,
+    })
+}
+
+// The V2 payloads follow the account lists of the V2 instructions in the Bubblegum client:
+// https://github.com/metaplex-foundation/mpl-bubblegum/tree/main/clients/rust/src/generated/instructions
+
+// `tree_creator` is optional and defaults to the payer.
+fn build_create_tree_v2_payload(
+    accounts: &CreateTreeV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = CreateTreeV2InstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::CreateTree {
+        tree_id: accounts.merkle_tree,
+        tree_creator: accounts.tree_creator.unwrap_or(accounts.payer),
+        payer: accounts.payer,
+        max_depth: args.max_depth,
+        max_buffer_size: args.max_buffer_size,
+        public: args.public,
+    })
+}
+
+// `tree_creator_or_delegate` defaults to the payer and `leaf_delegate` to the leaf owner.
+fn build_mint_v2_payload(
+    accounts: &MintV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = MintV2InstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::MintV2 {
+        args: args.metadata,
+        asset_data: args.asset_data,
+        asset_data_schema: args.asset_data_schema,
+        authority: accounts.tree_creator_or_delegate.unwrap_or(accounts.payer),
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        leaf_delegate: accounts.leaf_delegate.unwrap_or(accounts.leaf_owner),
+    })
+}
+
+fn build_transfer_v2_payload(
+    accounts: &TransferV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = LeafInstructionV2Args::try_from_slice(ix_data)?;
+
+    Ok(Payload::TransferV2 {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        leaf_delegate: accounts.leaf_delegate.unwrap_or(accounts.leaf_owner),
+        new_leaf_owner: accounts.new_leaf_owner,
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+        nonce: args.nonce,
+        index: args.index,
+    })
+}
+
+fn build_burn_v2_payload(
+    accounts: &BurnV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = LeafInstructionV2Args::try_from_slice(ix_data)?;
+
+    Ok(Payload::BurnV2 {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        leaf_delegate: accounts.leaf_delegate.unwrap_or(accounts.leaf_owner),
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+        nonce: args.nonce,
+        index: args.index,
+    })
+}
+
+fn build_delegate_v2_payload(
+    accounts: &DelegateV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = CollectionLeafInstructionV2Args::try_from_slice(ix_data)?;
+
+    Ok(Payload::DelegateV2 {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        previous_leaf_delegate: accounts
+            .previous_leaf_delegate
+            .unwrap_or(accounts.leaf_owner),
+        new_leaf_delegate: accounts.new_leaf_delegate,
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        collection_hash: args.collection_hash,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+        nonce: args.nonce,
+        index: args.index,
+    })
+}
+
+fn build_freeze_v2_payload(
+    accounts: &FreezeV2Accounts,
+    ix_data: &[u8],
+    freeze: bool,
+) -> Result<Payload, BlockbusterError> {
+    let args = CollectionLeafInstructionV2Args::try_from_slice(ix_data)?;
+
+    Ok(build_freeze_v2(
+        accounts.merkle_tree,
+        accounts.leaf_owner,
+        accounts.leaf_delegate.unwrap_or(accounts.leaf_owner),
+        freeze,
+        args,
+    ))
+}
+
+// The leaf delegate signs this instruction and is revoked by it.
+fn build_thaw_and_revoke_v2_payload(
+    accounts: &ThawAndRevokeV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = CollectionLeafInstructionV2Args::try_from_slice(ix_data)?;
+
+    Ok(build_freeze_v2(
+        accounts.merkle_tree,
+        accounts.leaf_owner,
+        accounts.leaf_delegate,
+        false,
+        args,
+    ))
+}
+
+fn build_freeze_v2(
+    tree_id: Pubkey,
+    leaf_owner: Pubkey,
+    leaf_delegate: Pubkey,
+    freeze: bool,
+    args: CollectionLeafInstructionV2Args,
+) -> Payload {
+    Payload::FreezeV2 {
         tree_id,
+        leaf_owner,
+        leaf_delegate,
+        freeze,
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        collection_hash: args.collection_hash,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+        nonce: args.nonce,
+        index: args.index,
+    }
+}
+
+fn build_set_non_transferable_v2_payload(
+    accounts: &SetNonTransferableV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = LeafInstructionV2Args::try_from_slice(ix_data)?;
+
+    Ok(Payload::SetNonTransferableV2 {
+        tree_id: accounts.merkle_tree,
+        leaf_owner: accounts.leaf_owner,
+        leaf_delegate: accounts.leaf_delegate.unwrap_or(accounts.leaf_owner),
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+        nonce: args.nonce,
+        index: args.index,
+    })
+}
+
+fn build_creator_verification_v2_payload(
+    accounts: &CreatorVerificationV2Accounts,
+    ix_data: &[u8],
+    verify: bool,
+) -> Result<Payload, BlockbusterError> {
+    let args = CreatorVerificationV2InstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::CreatorVerificationV2 {
+        metadata: args.metadata,
+        creator: accounts.creator,
+        verify,
+    })
+}
+
+// A missing `new_core_collection` account removes the asset from its collection.
+fn build_set_collection_v2_payload(
+    accounts: &SetCollectionV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = SetCollectionV2InstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::SetCollectionV2 {
+        metadata: args.metadata,
+        new_collection: accounts.new_core_collection,
+        tree_id: accounts.merkle_tree,
+    })
+}
+
+fn build_update_metadata_v2_payload(
+    accounts: &UpdateMetadataV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = UpdateMetadataV2InstructionArgs::try_from_slice(ix_data)?;
+
+    let updated_metadata = apply_update_args_v2(&args.current_metadata, &args.update_args);
+
+    Ok(Payload::UpdateMetadataV2 {
+        current_metadata: args.current_metadata,
+        update_args: args.update_args,
+        updated_metadata,
+        tree_id: accounts.merkle_tree,
+    })
+}
+
+fn build_update_asset_data_v2_payload(
+    accounts: &UpdateAssetDataV2Accounts,
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = UpdateAssetDataV2InstructionArgs::try_from_slice(ix_data)?;
+
+    Ok(Payload::UpdateAssetDataV2 {
+        previous_asset_data: args.previous_asset_data,
+        new_asset_data: args.new_asset_data,
+        new_asset_data_schema: args.new_asset_data_schema,
+        tree_id: accounts.merkle_tree,
     })
 }
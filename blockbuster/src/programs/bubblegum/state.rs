@@ -0,0 +1,59 @@
+use super::LeafSchema;
+use crate::{error::BlockbusterError, program_handler::ParseResult, programs::ProgramParseResult};
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use mpl_bubblegum::accounts::TreeConfig;
+use solana_sdk::pubkey::Pubkey;
+
+const TREE_CONFIG_DISCRIMINATOR: [u8; 8] = [122, 245, 175, 248, 171, 34, 0, 207];
+const VOUCHER_DISCRIMINATOR: [u8; 8] = [191, 204, 149, 234, 213, 165, 13, 65];
+
+/// Voucher created by `Redeem`, holding the redeemed leaf until it is decompressed or cancelled.
+///
+/// Same layout as `mpl_bubblegum::accounts::Voucher`, using the versioned `LeafSchema`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Voucher {
+    pub discriminator: [u8; 8],
+    pub leaf_schema: LeafSchema,
+    pub index: u32,
+    pub merkle_tree: Pubkey,
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum BubblegumAccount {
+    TreeConfig(TreeConfig),
+    Voucher(Voucher),
+}
+
+impl ParseResult for BubblegumAccount {
+    fn result(&self) -> &Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+    fn result_type(&self) -> ProgramParseResult {
+        ProgramParseResult::BubblegumAccount(self)
+    }
+}
+
+impl BubblegumAccount {
+    pub fn from_account_data(account_data: &[u8]) -> Result<Self, BlockbusterError> {
+        let discriminator: [u8; 8] = account_data
+            .get(..8)
+            .and_then(|d| d.try_into().ok())
+            .ok_or(BlockbusterError::InvalidDataLength)?;
+
+        let account = match discriminator {
+            TREE_CONFIG_DISCRIMINATOR => {
+                BubblegumAccount::TreeConfig(TreeConfig::from_bytes(account_data)?)
+            }
+            VOUCHER_DISCRIMINATOR => {
+                let mut data = account_data;
+                BubblegumAccount::Voucher(Voucher::deserialize(&mut data)?)
+            }
+            _ => return Err(BlockbusterError::UnknownAccountDiscriminator),
+        };
+
+        Ok(account)
+    }
+}
@@ -0,0 +1,117 @@
+//! Instruction argument layouts for the Bubblegum V2 instruction set.
+//!
+//! The pinned `mpl-bubblegum` client only knows the V1 instructions, so the V2 layouts are
+//! declared here. Each struct is the borsh encoding of the instruction data following the anchor
+//! discriminator.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_bubblegum::types::{Creator, TokenStandard, UpdateArgs};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct MetadataArgsV2 {
+    /// The name of the asset
+    pub name: String,
+    /// The symbol for the asset
+    pub symbol: String,
+    /// URI pointing to JSON representing the asset
+    pub uri: String,
+    /// Royalty basis points that goes to creators in secondary sales (0-10000)
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub token_standard: Option<TokenStandard>,
+    pub creators: Vec<Creator>,
+    /// MPL Core collection the asset belongs to
+    pub collection: Option<Pubkey>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum AssetDataSchema {
+    Binary,
+    Json,
+    MsgPack,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct CreateTreeV2InstructionArgs {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub public: Option<bool>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct MintV2InstructionArgs {
+    pub metadata: MetadataArgsV2,
+    pub asset_data: Option<Vec<u8>>,
+    pub asset_data_schema: Option<AssetDataSchema>,
+}
+
+/// Shared by `TransferV2`, `BurnV2` and `SetNonTransferableV2`, which read the collection from
+/// the `core_collection` account instead of taking its hash as an argument.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LeafInstructionV2Args {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+    pub nonce: u64,
+    pub index: u32,
+}
+
+/// Shared by `DelegateV2`, `DelegateAndFreezeV2`, `FreezeV2`, `ThawV2` and `ThawAndRevokeV2`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct CollectionLeafInstructionV2Args {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub collection_hash: Option<[u8; 32]>,
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+    pub nonce: u64,
+    pub index: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct UpdateMetadataV2InstructionArgs {
+    pub root: [u8; 32],
+    pub current_metadata: MetadataArgsV2,
+    pub update_args: UpdateArgs,
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+    pub nonce: u64,
+    pub index: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct UpdateAssetDataV2InstructionArgs {
+    pub root: [u8; 32],
+    pub previous_asset_data: Option<Vec<u8>>,
+    pub new_asset_data: Option<Vec<u8>>,
+    pub new_asset_data_schema: Option<AssetDataSchema>,
+    pub flags: Option<u8>,
+    pub nonce: u64,
+    pub index: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetCollectionV2InstructionArgs {
+    pub root: [u8; 32],
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+    pub nonce: u64,
+    pub index: u32,
+    pub metadata: MetadataArgsV2,
+}
+
+/// Shared by `VerifyCreatorV2` and `UnverifyCreatorV2`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct CreatorVerificationV2InstructionArgs {
+    pub root: [u8; 32],
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+    pub nonce: u64,
+    pub index: u32,
+    pub metadata: MetadataArgsV2,
+}
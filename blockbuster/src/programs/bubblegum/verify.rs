@@ -0,0 +1,59 @@
+use super::{BubblegumInstruction, Payload};
+use crate::error::BlockbusterError;
+use mpl_bubblegum::hash::{hash_creators, hash_metadata};
+
+impl BubblegumInstruction {
+    /// Opt-in check that the data hash and creator hash of the emitted leaf match the ones
+    /// recomputed from the instruction's `MetadataArgs`.
+    ///
+    /// Covers `MintV1`, `MintToCollectionV1`, `VerifyCreator`, `UnverifyCreator` and
+    /// `UpdateMetadata`. Other instructions, or instructions without a leaf event, are accepted
+    /// as is.
+    pub fn verify_metadata_hashes(&self) -> Result<(), BlockbusterError> {
+        let leaf = match &self.leaf_update {
+            Some(leaf) => leaf,
+            None => return Ok(()),
+        };
+
+        let metadata = match &self.payload {
+            Some(Payload::MintV1 { args, .. }) => args.clone(),
+            Some(Payload::CreatorVerification {
+                metadata,
+                creator,
+                verify,
+            }) => {
+                let mut metadata = metadata.clone();
+                for c in metadata.creators.iter_mut() {
+                    if c.address == *creator {
+                        c.verified = *verify;
+                    }
+                }
+                metadata
+            }
+            Some(Payload::UpdateMetadata {
+                updated_metadata, ..
+            }) => updated_metadata.clone(),
+            _ => return Ok(()),
+        };
+
+        let computed = hash_metadata(&metadata)?;
+        let data_hash = leaf.schema.data_hash();
+        if computed != data_hash {
+            return Err(BlockbusterError::DataHashMismatch {
+                computed,
+                leaf: data_hash,
+            });
+        }
+
+        let computed = hash_creators(&metadata.creators);
+        let creator_hash = leaf.schema.creator_hash();
+        if computed != creator_hash {
+            return Err(BlockbusterError::CreatorHashMismatch {
+                computed,
+                leaf: creator_hash,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+/// Declares a struct naming the accounts of an instruction, in instruction order, with a
+/// `try_from_keys` constructor reading them from `InstructionBundle::keys`.
+///
+/// Accounts marked `optional` follow Anchor's convention of passing the program ID in place of an
+/// account that was not provided, and are `None` in that case. A missing required account is
+/// reported as `BlockbusterError::MissingInstructionAccount`.
+macro_rules! instruction_accounts {
+    (
+        program = $program:expr;
+        $(
+            $(#[$meta:meta])*
+            pub struct $name:ident {
+                $($account:ident: $kind:ident),* $(,)?
+            }
+        )*
+    ) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name {
+                $(pub $account: instruction_accounts!(@type $kind),)*
+            }
+
+            impl $name {
+                pub fn try_from_keys(
+                    keys: &[solana_sdk::pubkey::Pubkey],
+                ) -> Result<Self, $crate::error::BlockbusterError> {
+                    let mut keys = keys.iter().copied();
+                    Ok(Self {
+                        $(
+                            $account: instruction_accounts!(
+                                @read $kind, keys, $program, $name, $account
+                            ),
+                        )*
+                    })
+                }
+            }
+        )*
+    };
+    (@type required) => {
+        solana_sdk::pubkey::Pubkey
+    };
+    (@type optional) => {
+        Option<solana_sdk::pubkey::Pubkey>
+    };
+    (@read required, $keys:ident, $program:expr, $name:ident, $account:ident) => {
+        $keys.next().ok_or(
+            $crate::error::BlockbusterError::MissingInstructionAccount {
+                accounts: stringify!($name),
+                account: stringify!($account),
+            },
+        )?
+    };
+    (@read optional, $keys:ident, $program:expr, $name:ident, $account:ident) => {
+        $keys.next().filter(|key| key != &$program)
+    };
+}
@@ -1,7 +1,12 @@
-use bubblegum::BubblegumInstruction;
+use account_compression::{AccountCompressionInstruction, ConcurrentMerkleTreeAccount};
+use bubblegum::{BubblegumAccount, BubblegumInstruction};
 use token_account::TokenProgramAccount;
-use token_metadata::TokenMetadataAccountState;
+use token_metadata::{TokenMetadataAccountState, TokenMetadataInstruction};
 
+#[macro_use]
+mod macros;
+
+pub mod account_compression;
 pub mod bubblegum;
 pub mod token_account;
 pub mod token_metadata;
@@ -21,8 +26,12 @@ pub mod token_metadata;
 // Candy Machine V2 (`mpl-candy-machine`) parsing was removed at the same time as V3 because even
 // though it did not depend on the `mpl-candy-machine` crate, it was also not being used by DAS.
 pub enum ProgramParseResult<'a> {
+    AccountCompression(&'a AccountCompressionInstruction),
+    ConcurrentMerkleTree(&'a ConcurrentMerkleTreeAccount),
     Bubblegum(&'a BubblegumInstruction),
+    BubblegumAccount(&'a BubblegumAccount),
     TokenMetadata(&'a TokenMetadataAccountState),
+    TokenMetadataInstruction(&'a TokenMetadataInstruction),
     TokenProgramAccount(&'a TokenProgramAccount),
     Unknown,
 }
@@ -0,0 +1,204 @@
+//! Named accounts of each Token Metadata instruction, in the order the program expects them.
+
+use super::token_metadata_id;
+
+instruction_accounts! {
+    program = token_metadata_id();
+
+    pub struct CreateMetadataAccountV3Accounts {
+        metadata: required,
+        mint: required,
+        mint_authority: required,
+        payer: required,
+        update_authority: required,
+        system_program: required,
+        rent: optional,
+    }
+
+    pub struct CreateAccounts {
+        metadata: required,
+        master_edition: optional,
+        mint: required,
+        authority: required,
+        payer: required,
+        update_authority: required,
+        system_program: required,
+        sysvar_instructions: required,
+        spl_token_program: optional,
+    }
+
+    pub struct UpdateAccounts {
+        authority: required,
+        delegate_record: optional,
+        token: optional,
+        mint: required,
+        metadata: required,
+        edition: optional,
+        payer: required,
+        system_program: required,
+        sysvar_instructions: required,
+        authorization_rules_program: optional,
+        authorization_rules: optional,
+    }
+
+    pub struct VerifyAccounts {
+        authority: required,
+        delegate_record: optional,
+        metadata: required,
+        collection_mint: optional,
+        collection_metadata: optional,
+        collection_master_edition: optional,
+        system_program: required,
+        sysvar_instructions: required,
+    }
+
+    pub struct UnverifyAccounts {
+        authority: required,
+        delegate_record: optional,
+        metadata: required,
+        collection_mint: optional,
+        collection_metadata: optional,
+        system_program: required,
+        sysvar_instructions: required,
+    }
+
+    pub struct BurnAccounts {
+        authority: required,
+        collection_metadata: optional,
+        metadata: required,
+        edition: optional,
+        mint: required,
+        token: required,
+        master_edition: optional,
+        master_edition_mint: optional,
+        master_edition_token: optional,
+        edition_marker: optional,
+        token_record: optional,
+        system_program: required,
+        sysvar_instructions: required,
+        spl_token_program: required,
+    }
+
+    pub struct MintAccounts {
+        token: required,
+        token_owner: optional,
+        metadata: required,
+        master_edition: optional,
+        token_record: optional,
+        mint: required,
+        authority: required,
+        delegate_record: optional,
+        payer: required,
+        system_program: required,
+        sysvar_instructions: required,
+        spl_token_program: required,
+        spl_ata_program: required,
+        authorization_rules_program: optional,
+        authorization_rules: optional,
+    }
+
+    pub struct TransferAccounts {
+        token: required,
+        token_owner: required,
+        destination_token: required,
+        destination_owner: required,
+        mint: required,
+        metadata: required,
+        edition: optional,
+        token_record: optional,
+        destination_token_record: optional,
+        authority: required,
+        payer: required,
+        system_program: required,
+        sysvar_instructions: required,
+        spl_token_program: required,
+        spl_ata_program: required,
+        authorization_rules_program: optional,
+        authorization_rules: optional,
+    }
+
+    /// Shared by `Delegate` and `Revoke`.
+    pub struct DelegateAccounts {
+        delegate_record: optional,
+        delegate: required,
+        metadata: required,
+        master_edition: optional,
+        token_record: optional,
+        mint: required,
+        token: optional,
+        authority: required,
+        payer: required,
+        system_program: required,
+        sysvar_instructions: required,
+        spl_token_program: optional,
+        authorization_rules_program: optional,
+        authorization_rules: optional,
+    }
+
+    /// Shared by `Lock` and `Unlock`.
+    pub struct LockAccounts {
+        authority: required,
+        token_owner: optional,
+        token: required,
+        mint: required,
+        metadata: required,
+        edition: optional,
+        token_record: optional,
+        payer: required,
+        system_program: required,
+        sysvar_instructions: required,
+        spl_token_program: optional,
+        authorization_rules_program: optional,
+        authorization_rules: optional,
+    }
+
+    /// The holder delegate accounts are only passed to `PrintV2`.
+    pub struct PrintAccounts {
+        edition_metadata: required,
+        edition: required,
+        edition_mint: required,
+        edition_token_account_owner: required,
+        edition_token_account: required,
+        edition_mint_authority: required,
+        edition_token_record: optional,
+        master_edition: required,
+        edition_marker_pda: required,
+        payer: required,
+        master_token_account_owner: required,
+        master_token_account: required,
+        master_metadata: required,
+        update_authority: required,
+        spl_token_program: required,
+        spl_ata_program: required,
+        sysvar_instructions: required,
+        system_program: required,
+        holder_delegate_record: optional,
+        delegate: optional,
+    }
+
+    pub struct SetCollectionSizeAccounts {
+        collection_metadata: required,
+        collection_authority: required,
+        collection_mint: required,
+        collection_authority_record: optional,
+    }
+}
+
+/// The named accounts of a parsed Token Metadata instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionAccounts {
+    CreateMetadataAccountV3(CreateMetadataAccountV3Accounts),
+    Create(CreateAccounts),
+    Update(UpdateAccounts),
+    Verify(VerifyAccounts),
+    Unverify(UnverifyAccounts),
+    Burn(BurnAccounts),
+    Mint(MintAccounts),
+    Transfer(TransferAccounts),
+    Delegate(DelegateAccounts),
+    Revoke(DelegateAccounts),
+    Lock(LockAccounts),
+    Unlock(LockAccounts),
+    Print(PrintAccounts),
+    SetCollectionSize(SetCollectionSizeAccounts),
+}
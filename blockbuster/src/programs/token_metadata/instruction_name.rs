@@ -0,0 +1,42 @@
+/// Token Metadata instructions known to the parser.
+///
+/// Versioned instructions are named after their family, the version is the variant of the args
+/// carried by `Payload` (for example `CreateArgs::V1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstructionName {
+    Unknown,
+    CreateMetadataAccountV3,
+    SetCollectionSize,
+    Burn,
+    Create,
+    Mint,
+    Delegate,
+    Revoke,
+    Lock,
+    Unlock,
+    Transfer,
+    Update,
+    Verify,
+    Unverify,
+    Print,
+}
+
+pub fn get_instruction_type(full_bytes: &[u8]) -> InstructionName {
+    match full_bytes.first() {
+        Some(33) => InstructionName::CreateMetadataAccountV3,
+        Some(34) => InstructionName::SetCollectionSize,
+        Some(41) => InstructionName::Burn,
+        Some(42) => InstructionName::Create,
+        Some(43) => InstructionName::Mint,
+        Some(44) => InstructionName::Delegate,
+        Some(45) => InstructionName::Revoke,
+        Some(46) => InstructionName::Lock,
+        Some(47) => InstructionName::Unlock,
+        Some(49) => InstructionName::Transfer,
+        Some(50) => InstructionName::Update,
+        Some(52) => InstructionName::Verify,
+        Some(53) => InstructionName::Unverify,
+        Some(55) => InstructionName::Print,
+        _ => InstructionName::Unknown,
+    }
+}
@@ -0,0 +1,235 @@
+use super::NormalizedMetadata;
+use crate::error::BlockbusterError;
+use borsh::BorshDeserialize;
+use mpl_token_metadata::{
+    accounts::Metadata,
+    types::{Creator, Key},
+};
+use solana_sdk::pubkey::Pubkey;
+
+// Borsh size of a `Creator`: address, verified and share.
+const CREATOR_SIZE: usize = 34;
+
+/// Fields of a `Metadata` account following the mint, in account order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+    SellerFeeBasisPoints,
+    Creators,
+    PrimarySaleHappened,
+    IsMutable,
+    EditionNonce,
+    TokenStandard,
+    Collection,
+    Uses,
+    CollectionDetails,
+    ProgrammableConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldIssue {
+    /// The data ended, or an earlier field could not be read, before the field. It holds its
+    /// default value.
+    Missing,
+    /// The field could not be decoded and holds its default value. The fields after it cannot be
+    /// located and are reported as `Missing`.
+    Unreadable,
+    /// Part of the field was recovered: a string with invalid UTF-8 or cut short, a creator list
+    /// cut short, or a boolean stored as a byte other than 0 or 1.
+    Repaired,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetadataFieldIssue {
+    pub field: MetadataField,
+    pub issue: FieldIssue,
+}
+
+/// A `Metadata` account decoded field by field, for accounts `Metadata::safe_deserialize`
+/// rejects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LenientMetadata {
+    pub metadata: Metadata,
+    /// Fields that were defaulted or repaired, empty when the account decoded cleanly.
+    pub issues: Vec<MetadataFieldIssue>,
+}
+
+impl LenientMetadata {
+    /// Decodes `account_data`, only failing when the key, update authority or mint cannot be
+    /// read.
+    pub fn from_account_data(account_data: &[u8]) -> Result<Self, BlockbusterError> {
+        if let Ok(metadata) = Metadata::safe_deserialize(account_data) {
+            return Ok(LenientMetadata {
+                metadata,
+                issues: Vec::new(),
+            });
+        }
+
+        let mut data = account_data;
+        let key = Key::deserialize(&mut data)?;
+        if key != Key::MetadataV1 {
+            return Err(BlockbusterError::InvalidAccountType);
+        }
+        let update_authority = Pubkey::deserialize(&mut data)?;
+        let mint = Pubkey::deserialize(&mut data)?;
+
+        let mut reader = FieldReader {
+            data,
+            stopped: false,
+            issues: Vec::new(),
+        };
+        let name = reader.string(MetadataField::Name);
+        let symbol = reader.string(MetadataField::Symbol);
+        let uri = reader.string(MetadataField::Uri);
+        let seller_fee_basis_points = reader
+            .field(MetadataField::SellerFeeBasisPoints)
+            .unwrap_or_default();
+        let creators = reader.creators();
+        let primary_sale_happened = reader.bool(MetadataField::PrimarySaleHappened);
+        let is_mutable = reader.bool(MetadataField::IsMutable);
+        let metadata = Metadata {
+            key,
+            update_authority,
+            mint,
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points,
+            creators,
+            primary_sale_happened,
+            is_mutable,
+            edition_nonce: reader.field(MetadataField::EditionNonce).flatten(),
+            token_standard: reader.field(MetadataField::TokenStandard).flatten(),
+            collection: reader.field(MetadataField::Collection).flatten(),
+            uses: reader.field(MetadataField::Uses).flatten(),
+            collection_details: reader.field(MetadataField::CollectionDetails).flatten(),
+            programmable_config: reader.field(MetadataField::ProgrammableConfig).flatten(),
+        };
+
+        Ok(LenientMetadata {
+            metadata,
+            issues: reader.issues,
+        })
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn normalized(&self) -> NormalizedMetadata {
+        NormalizedMetadata::from_metadata(&self.metadata)
+    }
+}
+
+// Reads fields in order, recording an issue for each field that is not read as is.
+struct FieldReader<'a> {
+    data: &'a [u8],
+    // Set once a field could not be decoded, as the position of the next field is unknown.
+    stopped: bool,
+    issues: Vec<MetadataFieldIssue>,
+}
+
+impl FieldReader<'_> {
+    fn report(&mut self, field: MetadataField, issue: FieldIssue) {
+        self.issues.push(MetadataFieldIssue { field, issue });
+        if issue == FieldIssue::Unreadable {
+            self.stopped = true;
+        }
+    }
+
+    // Whether the field can be read, reporting it as missing otherwise.
+    fn available(&mut self, field: MetadataField) -> bool {
+        if self.stopped || self.data.is_empty() {
+            self.report(field, FieldIssue::Missing);
+            return false;
+        }
+        true
+    }
+
+    fn field<T: BorshDeserialize>(&mut self, field: MetadataField) -> Option<T> {
+        if !self.available(field) {
+            return None;
+        }
+        let mut data = self.data;
+        match T::deserialize(&mut data) {
+            Ok(value) => {
+                self.data = data;
+                Some(value)
+            }
+            Err(_) => {
+                self.report(field, FieldIssue::Unreadable);
+                None
+            }
+        }
+    }
+
+    fn bool(&mut self, field: MetadataField) -> bool {
+        let byte: u8 = self.field(field).unwrap_or_default();
+        if byte > 1 {
+            self.report(field, FieldIssue::Repaired);
+        }
+        byte != 0
+    }
+
+    fn string(&mut self, field: MetadataField) -> String {
+        let Some(len) = self.field::<u32>(field) else {
+            return String::new();
+        };
+        let len = len as usize;
+        let mut repaired = false;
+        let bytes = if len > self.data.len() {
+            // The string runs past the end of the account, nothing can follow it.
+            repaired = true;
+            self.stopped = true;
+            std::mem::take(&mut self.data)
+        } else {
+            let (bytes, rest) = self.data.split_at(len);
+            self.data = rest;
+            bytes
+        };
+        let value = String::from_utf8_lossy(bytes);
+        if repaired || std::str::from_utf8(bytes).is_err() {
+            self.report(field, FieldIssue::Repaired);
+        }
+        value.into_owned()
+    }
+
+    fn creators(&mut self) -> Option<Vec<Creator>> {
+        let field = MetadataField::Creators;
+        match self.field::<u8>(field)? {
+            0 => return None,
+            1 => {}
+            _ => {
+                self.report(field, FieldIssue::Unreadable);
+                return None;
+            }
+        }
+        let len = self.field::<u32>(field)? as usize;
+        let available = self.data.len() / CREATOR_SIZE;
+        let mut creators = Vec::with_capacity(len.min(available));
+        let mut repaired = false;
+        for chunk in self.data.chunks_exact(CREATOR_SIZE).take(len) {
+            let address = Pubkey::try_from(&chunk[..32]).unwrap_or_default();
+            repaired |= chunk[32] > 1;
+            creators.push(Creator {
+                address,
+                verified: chunk[32] != 0,
+                share: chunk[33],
+            });
+        }
+        if len > available {
+            // The creators run past the end of the account, nothing can follow them.
+            repaired = true;
+            self.stopped = true;
+            self.data = &[];
+        } else {
+            self.data = &self.data[len * CREATOR_SIZE..];
+        }
+        if repaired {
+            self.report(field, FieldIssue::Repaired);
+        }
+        Some(creators)
+    }
+}
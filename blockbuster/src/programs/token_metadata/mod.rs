@@ -1,5 +1,6 @@
 use crate::{
     error::BlockbusterError,
+    instruction::InstructionBundle,
     program_handler::{ParseResult, ProgramParser},
     programs::ProgramParseResult,
 };
@@ -9,11 +10,33 @@ use solana_sdk::{borsh0_10::try_from_slice_unchecked, pubkey::Pubkey, pubkeys};
 use mpl_token_metadata::{
     accounts::{
         CollectionAuthorityRecord, DeprecatedMasterEditionV1, Edition, EditionMarker,
-        MasterEdition, Metadata, UseAuthorityRecord,
+        EditionMarkerV2, HolderDelegateRecord, MasterEdition, Metadata, MetadataDelegateRecord,
+        TokenOwnedEscrow, TokenRecord, UseAuthorityRecord,
+    },
+    instructions::CreateMetadataAccountV3InstructionArgs,
+    types::{
+        BurnArgs, CreateArgs, DelegateArgs, Key, LockArgs, MintArgs, PrintArgs, RevokeArgs,
+        SetCollectionSizeArgs, TransferArgs, UnlockArgs, UpdateArgs, VerificationArgs,
     },
-    types::Key,
 };
 
+pub mod accounts;
+mod instruction_name;
+mod lenient;
+mod normalized;
+pub mod pda;
+mod state;
+
+use accounts::{
+    BurnAccounts, CreateAccounts, CreateMetadataAccountV3Accounts, DelegateAccounts,
+    InstructionAccounts, LockAccounts, MintAccounts, PrintAccounts, SetCollectionSizeAccounts,
+    TransferAccounts, UnverifyAccounts, UpdateAccounts, VerifyAccounts,
+};
+pub use instruction_name::{get_instruction_type, InstructionName};
+pub use lenient::{FieldIssue, LenientMetadata, MetadataField, MetadataFieldIssue};
+pub use normalized::{NormalizedMetadata, SanitizationIssue, SanitizationWarning};
+pub use state::{Reservation, ReservationListV1, ReservationListV2, ReservationV1};
+
 pubkeys!(
     token_metadata_id,
     "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
@@ -24,10 +47,20 @@ pub enum TokenMetadataAccountData {
     EditionV1(Edition),
     MasterEditionV1(DeprecatedMasterEditionV1),
     MetadataV1(Metadata),
+    /// A `Metadata` account `Metadata::safe_deserialize` rejects, with its defaulted or repaired
+    /// fields.
+    PartialMetadataV1(LenientMetadata),
     MasterEditionV2(MasterEdition),
     EditionMarker(EditionMarker),
     UseAuthorityRecord(UseAuthorityRecord),
     CollectionAuthorityRecord(CollectionAuthorityRecord),
+    ReservationListV1(ReservationListV1),
+    ReservationListV2(ReservationListV2),
+    TokenOwnedEscrow(TokenOwnedEscrow),
+    TokenRecord(TokenRecord),
+    MetadataDelegate(MetadataDelegateRecord),
+    EditionMarkerV2(EditionMarkerV2),
+    HolderDelegate(HolderDelegateRecord),
     EmptyAccount,
 }
 
@@ -36,6 +69,22 @@ pub struct TokenMetadataAccountState {
     pub data: TokenMetadataAccountData,
 }
 
+impl TokenMetadataAccountState {
+    /// The `Metadata` of a `MetadataV1` or `PartialMetadataV1` account, `None` for other accounts.
+    pub fn metadata(&self) -> Option<&Metadata> {
+        match &self.data {
+            TokenMetadataAccountData::MetadataV1(metadata) => Some(metadata),
+            TokenMetadataAccountData::PartialMetadataV1(lenient) => Some(&lenient.metadata),
+            _ => None,
+        }
+    }
+
+    /// The normalized strings of a `Metadata` account, `None` for other accounts.
+    pub fn normalized_metadata(&self) -> Option<NormalizedMetadata> {
+        self.metadata().map(NormalizedMetadata::from_metadata)
+    }
+}
+
 impl ParseResult for TokenMetadataAccountState {
     fn result(&self) -> &Self
     where
@@ -48,6 +97,54 @@ impl ParseResult for TokenMetadataAccountState {
     }
 }
 
+/// The typed args of a parsed Token Metadata instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Payload {
+    CreateMetadataAccountV3(CreateMetadataAccountV3InstructionArgs),
+    Create(CreateArgs),
+    Update(UpdateArgs),
+    Verify(VerificationArgs),
+    Unverify(VerificationArgs),
+    Burn(BurnArgs),
+    Mint(MintArgs),
+    Transfer(TransferArgs),
+    Delegate(DelegateArgs),
+    Revoke(RevokeArgs),
+    Lock(LockArgs),
+    Unlock(UnlockArgs),
+    Print(PrintArgs),
+    SetCollectionSize(SetCollectionSizeArgs),
+}
+
+pub struct TokenMetadataInstruction {
+    pub instruction: InstructionName,
+    pub payload: Option<Payload>,
+    /// The named accounts of the instruction.
+    pub accounts: Option<InstructionAccounts>,
+}
+
+impl TokenMetadataInstruction {
+    pub const fn new(ix: InstructionName) -> Self {
+        TokenMetadataInstruction {
+            instruction: ix,
+            payload: None,
+            accounts: None,
+        }
+    }
+}
+
+impl ParseResult for TokenMetadataInstruction {
+    fn result(&self) -> &Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+    fn result_type(&self) -> ProgramParseResult {
+        ProgramParseResult::TokenMetadataInstruction(self)
+    }
+}
+
 pub struct TokenMetadataParser;
 
 impl ProgramParser for TokenMetadataParser {
@@ -63,7 +160,7 @@ impl ProgramParser for TokenMetadataParser {
     }
 
     fn handles_instructions(&self) -> bool {
-        false
+        true
     }
 
     fn handle_account(
@@ -127,21 +224,168 @@ impl ProgramParser for TokenMetadataParser {
                 }
             }
             Key::MetadataV1 => {
-                let account = Metadata::safe_deserialize(account_data)?;
+                let account = LenientMetadata::from_account_data(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.metadata.key,
+                    data: if account.is_complete() {
+                        TokenMetadataAccountData::MetadataV1(account.metadata)
+                    } else {
+                        TokenMetadataAccountData::PartialMetadataV1(account)
+                    },
+                }
+            }
+            Key::ReservationListV1 => {
+                let account: ReservationListV1 = try_from_slice_unchecked(account_data)?;
 
                 TokenMetadataAccountState {
                     key: account.key,
-                    data: TokenMetadataAccountData::MetadataV1(account),
+                    data: TokenMetadataAccountData::ReservationListV1(account),
+                }
+            }
+            Key::ReservationListV2 => {
+                let account: ReservationListV2 = try_from_slice_unchecked(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.key,
+                    data: TokenMetadataAccountData::ReservationListV2(account),
+                }
+            }
+            Key::TokenOwnedEscrow => {
+                let account: TokenOwnedEscrow = try_from_slice_unchecked(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.key,
+                    data: TokenMetadataAccountData::TokenOwnedEscrow(account),
+                }
+            }
+            Key::TokenRecord => {
+                // Token records written before `locked_transfer` existed are shorter.
+                let account = TokenRecord::safe_deserialize(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.key,
+                    data: TokenMetadataAccountData::TokenRecord(account),
+                }
+            }
+            Key::MetadataDelegate => {
+                let account: MetadataDelegateRecord = try_from_slice_unchecked(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.key,
+                    data: TokenMetadataAccountData::MetadataDelegate(account),
+                }
+            }
+            Key::EditionMarkerV2 => {
+                let account: EditionMarkerV2 = try_from_slice_unchecked(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.key,
+                    data: TokenMetadataAccountData::EditionMarkerV2(account),
+                }
+            }
+            Key::HolderDelegate => {
+                let account: HolderDelegateRecord = try_from_slice_unchecked(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.key,
+                    data: TokenMetadataAccountData::HolderDelegate(account),
                 }
             }
             Key::Uninitialized => {
                 return Err(BlockbusterError::UninitializedAccount);
             }
-            _ => {
-                return Err(BlockbusterError::AccountTypeNotImplemented);
-            }
         };
 
         Ok(Box::new(token_metadata_account_state))
     }
+
+    fn handle_instruction(
+        &self,
+        bundle: &InstructionBundle,
+    ) -> Result<Box<(dyn ParseResult + 'static)>, BlockbusterError> {
+        let InstructionBundle {
+            instruction, keys, ..
+        } = bundle;
+        let outer_ix_data = match instruction {
+            Some(cix) => cix.data.as_ref(),
+            _ => return Err(BlockbusterError::DeserializationError),
+        };
+        let mut tm_inst = TokenMetadataInstruction::new(get_instruction_type(outer_ix_data));
+        // Versioned instructions follow the discriminator with an args enum whose variant is the
+        // version.
+        let Some(ix_data) = outer_ix_data.get(1..) else {
+            return Ok(Box::new(tm_inst));
+        };
+
+        let (payload, accounts) = match tm_inst.instruction {
+            InstructionName::CreateMetadataAccountV3 => (
+                Payload::CreateMetadataAccountV3(
+                    CreateMetadataAccountV3InstructionArgs::try_from_slice(ix_data)?,
+                ),
+                InstructionAccounts::CreateMetadataAccountV3(
+                    CreateMetadataAccountV3Accounts::try_from_keys(keys)?,
+                ),
+            ),
+            InstructionName::SetCollectionSize => (
+                Payload::SetCollectionSize(SetCollectionSizeArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::SetCollectionSize(SetCollectionSizeAccounts::try_from_keys(
+                    keys,
+                )?),
+            ),
+            InstructionName::Burn => (
+                Payload::Burn(BurnArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Burn(BurnAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Create => (
+                Payload::Create(CreateArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Create(CreateAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Mint => (
+                Payload::Mint(MintArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Mint(MintAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Delegate => (
+                Payload::Delegate(DelegateArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Delegate(DelegateAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Revoke => (
+                Payload::Revoke(RevokeArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Revoke(DelegateAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Lock => (
+                Payload::Lock(LockArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Lock(LockAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Unlock => (
+                Payload::Unlock(UnlockArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Unlock(LockAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Transfer => (
+                Payload::Transfer(TransferArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Transfer(TransferAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Update => (
+                Payload::Update(UpdateArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Update(UpdateAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Verify => (
+                Payload::Verify(VerificationArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Verify(VerifyAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Unverify => (
+                Payload::Unverify(VerificationArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Unverify(UnverifyAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Print => (
+                Payload::Print(PrintArgs::try_from_slice(ix_data)?),
+                InstructionAccounts::Print(PrintAccounts::try_from_keys(keys)?),
+            ),
+            InstructionName::Unknown => return Ok(Box::new(tm_inst)),
+        };
+        tm_inst.payload = Some(payload);
+        tm_inst.accounts = Some(accounts);
+
+        Ok(Box::new(tm_inst))
+    }
 }
@@ -0,0 +1,89 @@
+use super::MetadataField;
+use mpl_token_metadata::accounts::Metadata;
+
+/// A change made to a string while normalizing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SanitizationIssue {
+    /// Control characters, other than the trailing `\0` padding, were removed.
+    ControlCharacters,
+    /// Leading or trailing whitespace was trimmed.
+    Whitespace,
+    /// The string holds U+FFFD, left in place of invalid UTF-8 by a lossy decoding.
+    ReplacementCharacter,
+    /// The URI is not empty but does not start with a valid scheme.
+    InvalidUriScheme,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SanitizationWarning {
+    pub field: MetadataField,
+    pub issue: SanitizationIssue,
+}
+
+/// The strings of a `Metadata` account in canonical form: without the `\0` padding, control
+/// characters and surrounding whitespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// Lowercase scheme of `uri`, `None` if it has none.
+    pub uri_scheme: Option<String>,
+    /// Changes made beyond removing the `\0` padding.
+    pub warnings: Vec<SanitizationWarning>,
+}
+
+impl NormalizedMetadata {
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        let mut warnings = Vec::new();
+        let name = normalize(MetadataField::Name, &metadata.name, &mut warnings);
+        let symbol = normalize(MetadataField::Symbol, &metadata.symbol, &mut warnings);
+        let uri = normalize(MetadataField::Uri, &metadata.uri, &mut warnings);
+        let uri_scheme = uri_scheme(&uri);
+        if uri_scheme.is_none() && !uri.is_empty() {
+            warnings.push(SanitizationWarning {
+                field: MetadataField::Uri,
+                issue: SanitizationIssue::InvalidUriScheme,
+            });
+        }
+
+        NormalizedMetadata {
+            name,
+            symbol,
+            uri,
+            uri_scheme,
+            warnings,
+        }
+    }
+}
+
+fn normalize(field: MetadataField, value: &str, warnings: &mut Vec<SanitizationWarning>) -> String {
+    let mut warn = |issue| warnings.push(SanitizationWarning { field, issue });
+
+    let unpadded = value.trim_end_matches('\0');
+    let mut normalized = unpadded
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>();
+    if normalized.len() != unpadded.len() {
+        warn(SanitizationIssue::ControlCharacters);
+    }
+    let trimmed = normalized.trim();
+    if trimmed.len() != normalized.len() {
+        normalized = trimmed.to_string();
+        warn(SanitizationIssue::Whitespace);
+    }
+    if normalized.contains(char::REPLACEMENT_CHARACTER) {
+        warn(SanitizationIssue::ReplacementCharacter);
+    }
+    normalized
+}
+
+// Scheme as defined by RFC 3986: a letter followed by letters, digits, `+`, `-` or `.`.
+fn uri_scheme(uri: &str) -> Option<String> {
+    let (scheme, _) = uri.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then(|| scheme.to_ascii_lowercase())
+}
@@ -0,0 +1,121 @@
+//! Addresses of the Token Metadata accounts derived from a mint.
+
+use super::TokenMetadataAccountState;
+use mpl_token_metadata::{
+    accounts::{
+        CollectionAuthorityRecord, EditionMarker, EditionMarkerV2, MasterEdition, Metadata,
+        TokenRecord, UseAuthorityRecord,
+    },
+    types::Key,
+    EDITION_MARKER_BIT_SIZE,
+};
+use solana_sdk::pubkey::Pubkey;
+
+/// A Token Metadata account address, with the seeds it is derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenMetadataPda {
+    Metadata {
+        mint: Pubkey,
+    },
+    /// The master edition, or print edition, of `mint`.
+    Edition {
+        mint: Pubkey,
+    },
+    /// The V1 marker tracking the print edition `edition` of the master edition of `mint`.
+    EditionMarker {
+        mint: Pubkey,
+        edition: u64,
+    },
+    EditionMarkerV2 {
+        mint: Pubkey,
+    },
+    TokenRecord {
+        mint: Pubkey,
+        token: Pubkey,
+    },
+    CollectionAuthorityRecord {
+        mint: Pubkey,
+        authority: Pubkey,
+    },
+    UseAuthorityRecord {
+        mint: Pubkey,
+        user: Pubkey,
+    },
+}
+
+impl TokenMetadataPda {
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            TokenMetadataPda::Metadata { mint }
+            | TokenMetadataPda::Edition { mint }
+            | TokenMetadataPda::EditionMarker { mint, .. }
+            | TokenMetadataPda::EditionMarkerV2 { mint }
+            | TokenMetadataPda::TokenRecord { mint, .. }
+            | TokenMetadataPda::CollectionAuthorityRecord { mint, .. }
+            | TokenMetadataPda::UseAuthorityRecord { mint, .. } => *mint,
+        }
+    }
+
+    /// The address and bump seed, derived with the `find_pda` helper of the account type.
+    pub fn find_address(&self) -> (Pubkey, u8) {
+        match self {
+            TokenMetadataPda::Metadata { mint } => Metadata::find_pda(mint),
+            TokenMetadataPda::Edition { mint } => MasterEdition::find_pda(mint),
+            TokenMetadataPda::EditionMarker { mint, edition } => {
+                EditionMarker::find_pda(mint, &(edition / EDITION_MARKER_BIT_SIZE).to_string())
+            }
+            TokenMetadataPda::EditionMarkerV2 { mint } => EditionMarkerV2::find_pda(mint),
+            TokenMetadataPda::TokenRecord { mint, token } => TokenRecord::find_pda(mint, token),
+            TokenMetadataPda::CollectionAuthorityRecord { mint, authority } => {
+                CollectionAuthorityRecord::find_pda(mint, authority)
+            }
+            TokenMetadataPda::UseAuthorityRecord { mint, user } => {
+                UseAuthorityRecord::find_pda(mint, user)
+            }
+        }
+    }
+
+    /// Whether an account with `key` can live at this address.
+    pub fn matches_key(&self, key: Key) -> bool {
+        match self {
+            TokenMetadataPda::Metadata { .. } => key == Key::MetadataV1,
+            TokenMetadataPda::Edition { .. } => matches!(
+                key,
+                Key::EditionV1 | Key::MasterEditionV1 | Key::MasterEditionV2
+            ),
+            TokenMetadataPda::EditionMarker { .. } => key == Key::EditionMarker,
+            TokenMetadataPda::EditionMarkerV2 { .. } => key == Key::EditionMarkerV2,
+            TokenMetadataPda::TokenRecord { .. } => key == Key::TokenRecord,
+            TokenMetadataPda::CollectionAuthorityRecord { .. } => {
+                key == Key::CollectionAuthorityRecord
+            }
+            TokenMetadataPda::UseAuthorityRecord { .. } => key == Key::UseAuthorityRecord,
+        }
+    }
+}
+
+impl TokenMetadataAccountState {
+    /// The PDA of a `Metadata` account, derived from the mint it holds. Other accounts do not
+    /// hold their seeds and `verify_address` has to be given them.
+    pub fn pda(&self) -> Option<TokenMetadataPda> {
+        self.metadata().map(|metadata| TokenMetadataPda::Metadata {
+            mint: metadata.mint,
+        })
+    }
+
+    /// Whether this account, stored at `address`, is the account `pda` derives.
+    ///
+    /// For example an `Edition` or `MasterEdition` belongs to a mint when
+    /// `TokenMetadataPda::Edition { mint }` verifies.
+    pub fn verify_address(&self, address: &Pubkey, pda: &TokenMetadataPda) -> bool {
+        if !pda.matches_key(self.key) {
+            return false;
+        }
+        if let Some(metadata) = self.metadata() {
+            if metadata.mint != pda.mint() {
+                return false;
+            }
+        }
+        pda.find_address().0 == *address
+    }
+}
@@ -0,0 +1,38 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_token_metadata::types::Key;
+use solana_sdk::pubkey::Pubkey;
+
+// Reservation lists belong to the removed printing token flow and are not part of the
+// `mpl_token_metadata` client, their layouts are kept here so existing accounts can be decoded.
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ReservationV1 {
+    pub address: Pubkey,
+    pub spots_remaining: u8,
+    pub total_spots: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ReservationListV1 {
+    pub key: Key,
+    pub master_edition: Pubkey,
+    pub supply_snapshot: Option<u64>,
+    pub reservations: Vec<ReservationV1>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Reservation {
+    pub address: Pubkey,
+    pub spots_remaining: u64,
+    pub total_spots: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ReservationListV2 {
+    pub key: Key,
+    pub master_edition: Pubkey,
+    pub supply_snapshot: Option<u64>,
+    pub reservations: Vec<Reservation>,
+    pub total_reservation_spots: u64,
+    pub current_reservation_spots: u64,
+}
@@ -0,0 +1,81 @@
+use solana_sdk::pubkey::Pubkey;
+use spl_account_compression::events::ChangeLogEventV1;
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::RangeInclusive,
+};
+
+/// Tracks the changelog sequences seen for each tree, in any order, and reports the missing ones.
+///
+/// Sequences start at 1, the changelog emitted when a tree is initialized (sequence 0) is ignored.
+#[derive(Default)]
+pub struct SequenceGapTracker {
+    // Disjoint, non-adjacent ranges of seen sequences per tree, keyed by range start.
+    seen: HashMap<Pubkey, BTreeMap<u64, u64>>,
+}
+
+impl SequenceGapTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe_changelog(&mut self, changelog: &ChangeLogEventV1) {
+        self.observe(changelog.id, changelog.seq);
+    }
+
+    pub fn observe(&mut self, tree: Pubkey, seq: u64) {
+        if seq == 0 {
+            return;
+        }
+        let ranges = self.seen.entry(tree).or_default();
+
+        let mut start = seq;
+        let mut end = seq;
+        if let Some((&prev_start, &prev_end)) = ranges.range(..=seq).next_back() {
+            if prev_end >= seq {
+                return;
+            }
+            if prev_end + 1 == seq {
+                start = prev_start;
+                ranges.remove(&prev_start);
+            }
+        }
+        if let Some(next_end) = seq.checked_add(1).and_then(|next| ranges.remove(&next)) {
+            end = next_end;
+        }
+        ranges.insert(start, end);
+    }
+
+    /// Highest sequence of `tree` such that every sequence from 1 up to it was seen, 0 if
+    /// sequence 1 was not seen.
+    pub fn highest_contiguous(&self, tree: &Pubkey) -> u64 {
+        self.seen
+            .get(tree)
+            .and_then(|ranges| ranges.get(&1).copied())
+            .unwrap_or(0)
+    }
+
+    /// Highest sequence seen for `tree`.
+    pub fn highest_seen(&self, tree: &Pubkey) -> Option<u64> {
+        self.seen
+            .get(tree)
+            .and_then(|ranges| ranges.values().next_back().copied())
+    }
+
+    /// Missing sequence ranges of `tree`, from sequence 1 up to the highest sequence seen.
+    pub fn gaps(&self, tree: &Pubkey) -> Vec<RangeInclusive<u64>> {
+        let mut gaps = Vec::new();
+        let mut next = 1;
+        for (&start, &end) in self.seen.get(tree).into_iter().flatten() {
+            if start > next {
+                gaps.push(next..=start - 1);
+            }
+            next = end.saturating_add(1);
+        }
+        gaps
+    }
+
+    pub fn trees(&self) -> impl Iterator<Item = &Pubkey> {
+        self.seen.keys()
+    }
+}
@@ -0,0 +1,39 @@
+//! Concurrent merkle tree state rebuilt from the changelogs emitted through the noop program.
+
+use solana_sdk::keccak;
+
+mod gaps;
+mod proof;
+mod replayer;
+
+pub use gaps::SequenceGapTracker;
+pub use proof::{
+    canopy_depth, compute_root, fill_proof_from_canopy, truncate_proof, update_proof, verify_proof,
+};
+pub use replayer::{ReplayStatus, ReplayedTree, TreeReplayer};
+
+/// A node of a concurrent merkle tree.
+pub type Node = [u8; 32];
+
+/// Largest depth supported by `spl_account_compression`.
+pub const MAX_DEPTH: u32 = 30;
+
+/// Parent of two sibling nodes.
+pub fn hash_pair(left: &Node, right: &Node) -> Node {
+    keccak::hashv(&[left.as_ref(), right.as_ref()]).to_bytes()
+}
+
+/// Root of an empty subtree of height `level`, where level 0 is an empty leaf.
+pub fn empty_node(level: u32) -> Node {
+    empty_nodes(level)[level as usize]
+}
+
+// Empty nodes of every level from 0 to `depth`.
+fn empty_nodes(depth: u32) -> Vec<Node> {
+    let mut nodes = Vec::with_capacity(depth as usize + 1);
+    nodes.push([0; 32]);
+    for level in 0..depth as usize {
+        nodes.push(hash_pair(&nodes[level], &nodes[level]));
+    }
+    nodes
+}
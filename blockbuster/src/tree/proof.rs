@@ -0,0 +1,110 @@
+use super::{empty_node, hash_pair, Node, MAX_DEPTH};
+use crate::error::BlockbusterError;
+use spl_account_compression::events::ChangeLogEventV1;
+
+/// Root of the tree holding `leaf` at `index`, given the sibling nodes from the leaf up.
+///
+/// Levels above the bits of `index` are taken as left children.
+pub fn compute_root(leaf: &Node, index: u32, proof: &[Node]) -> Node {
+    proof
+        .iter()
+        .enumerate()
+        .fold(*leaf, |node, (level, sibling)| {
+            let bit = u32::try_from(level)
+                .ok()
+                .and_then(|level| index.checked_shr(level))
+                .unwrap_or(0);
+            if bit & 1 == 0 {
+                hash_pair(&node, sibling)
+            } else {
+                hash_pair(sibling, &node)
+            }
+        })
+}
+
+/// Whether `proof` proves `leaf` at `index` under `root`. Proofs longer than `MAX_DEPTH`, and
+/// indexes beyond the leaves of a tree as deep as the proof, are rejected.
+pub fn verify_proof(root: &Node, leaf: &Node, index: u32, proof: &[Node]) -> bool {
+    proof.len() <= MAX_DEPTH as usize
+        && u64::from(index) < 1u64 << proof.len()
+        && compute_root(leaf, index, proof) == *root
+}
+
+/// The part of a full proof that is not cached in a canopy of `canopy_depth` levels.
+pub fn truncate_proof(proof: &[Node], canopy_depth: u32) -> &[Node] {
+    &proof[..proof.len().saturating_sub(canopy_depth as usize)]
+}
+
+/// Number of levels of a canopy of `canopy_len` nodes, `None` if the last level is incomplete.
+pub fn canopy_depth(canopy_len: usize) -> Option<u32> {
+    // A canopy of depth `d` holds `2^(d + 1) - 2` nodes.
+    let nodes = canopy_len.checked_add(2)?;
+    if nodes.is_power_of_two() {
+        Some(nodes.trailing_zeros() - 1)
+    } else {
+        None
+    }
+}
+
+/// Completes a proof truncated by `truncate_proof` with the nodes cached in `canopy`, as found in
+/// `ConcurrentMerkleTreeAccount::canopy`.
+pub fn fill_proof_from_canopy(
+    proof: &[Node],
+    index: u32,
+    depth: u32,
+    canopy: &[Node],
+) -> Result<Vec<Node>, BlockbusterError> {
+    let canopy_depth = canopy_depth(canopy.len())
+        .filter(|canopy_depth| *canopy_depth <= depth)
+        .ok_or(BlockbusterError::InvalidCanopyLength(canopy.len()))?;
+    let expected = (depth - canopy_depth) as usize;
+    if proof.len() != expected {
+        return Err(BlockbusterError::ProofLengthMismatch {
+            expected,
+            actual: proof.len(),
+        });
+    }
+    if u64::from(index) >= 1u64 << depth {
+        return Err(BlockbusterError::LeafIndexOutOfRange { index, depth });
+    }
+
+    let mut full_proof = proof.to_vec();
+    // Heap index of the ancestor of the leaf on the lowest canopy level, the canopy starts at 2.
+    let mut heap_index = ((1u64 << depth) + u64::from(index)) >> expected;
+    let mut level = depth - canopy_depth;
+    while heap_index > 1 {
+        let node = canopy[(heap_index ^ 1) as usize - 2];
+        // Canopy nodes that were never written are left zeroed.
+        full_proof.push(if node == [0; 32] {
+            empty_node(level)
+        } else {
+            node
+        });
+        heap_index >>= 1;
+        level += 1;
+    }
+    Ok(full_proof)
+}
+
+/// Brings the proof of the leaf at `index` up to date with `changelog`, which must be the next
+/// change to the tree. The changelog path replaces the one sibling shared with the changed leaf.
+///
+/// Returns `false`, leaving the proof untouched, when the changelog replaced the leaf at `index`
+/// itself: the siblings are unchanged but the proof no longer holds for the previous leaf. Also
+/// returns `false` when the leaves diverge above the proof or the changelog path, which happens
+/// when either does not belong to the tree of the other.
+pub fn update_proof(proof: &mut [Node], index: u32, changelog: &ChangeLogEventV1) -> bool {
+    if changelog.index == index {
+        return false;
+    }
+    // The sibling on the path of the changed leaf sits just below the highest level where the
+    // paths of both leaves diverge.
+    let level = (31 - (changelog.index ^ index).leading_zeros()) as usize;
+    match (proof.get_mut(level), changelog.path.get(level)) {
+        (Some(sibling), Some(path_node)) => {
+            *sibling = path_node.node;
+            true
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,211 @@
+use super::{empty_nodes, Node, MAX_DEPTH};
+use crate::error::BlockbusterError;
+use solana_sdk::pubkey::Pubkey;
+use spl_account_compression::events::ChangeLogEventV1;
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+
+/// Outcome of applying a changelog to a replayed tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayStatus {
+    /// The changelog was applied, along with any pending changelogs it made contiguous.
+    Applied,
+    /// The changelog is ahead of the tree and is held until the missing sequences arrive.
+    Pending,
+    /// The changelog was already applied.
+    Stale,
+}
+
+// A changelog waiting for the sequences before it, reduced to what is needed to apply it.
+struct PendingChange {
+    index: u32,
+    path: Vec<Node>,
+}
+
+/// State of one concurrent merkle tree rebuilt from its changelogs.
+///
+/// Only nodes written by a changelog are stored, every other node is the empty node of its level.
+pub struct ReplayedTree {
+    id: Pubkey,
+    depth: u32,
+    seq: u64,
+    leaf_count: u64,
+    // Nodes keyed by heap index: the root is 1 and the leaves start at `1 << depth`.
+    nodes: HashMap<u64, Node>,
+    empty_nodes: Vec<Node>,
+    pending: BTreeMap<u64, PendingChange>,
+}
+
+impl ReplayedTree {
+    /// Fails with `TreeDepthTooLarge` when `depth` is above `MAX_DEPTH`.
+    pub fn new(id: Pubkey, depth: u32) -> Result<Self, BlockbusterError> {
+        if depth > MAX_DEPTH {
+            return Err(BlockbusterError::TreeDepthTooLarge(depth));
+        }
+        Ok(ReplayedTree {
+            id,
+            depth,
+            seq: 0,
+            leaf_count: 0,
+            nodes: HashMap::new(),
+            empty_nodes: empty_nodes(depth),
+            pending: BTreeMap::new(),
+        })
+    }
+
+    pub const fn id(&self) -> Pubkey {
+        self.id
+    }
+
+    pub const fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Sequence number of the last applied changelog, 0 before any was applied.
+    pub const fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Number of leaves up to the highest leaf index written so far.
+    pub const fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// Number of changelogs held until the sequences before them are applied.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn root(&self) -> Node {
+        self.node(1, self.depth)
+    }
+
+    pub fn leaf(&self, index: u32) -> Result<Node, BlockbusterError> {
+        let heap_index = self.leaf_heap_index(index)?;
+        Ok(self.node(heap_index, 0))
+    }
+
+    /// Sibling nodes from the leaf up to, but excluding, the root.
+    pub fn proof(&self, index: u32) -> Result<Vec<Node>, BlockbusterError> {
+        let mut heap_index = self.leaf_heap_index(index)?;
+        let mut proof = Vec::with_capacity(self.depth as usize);
+        for level in 0..self.depth {
+            proof.push(self.node(heap_index ^ 1, level));
+            heap_index >>= 1;
+        }
+        Ok(proof)
+    }
+
+    /// The `canopy_depth` upper levels of the tree below the root, in heap order, as stored in
+    /// `ConcurrentMerkleTreeAccount::canopy`: nodes no changelog has written are zeroed.
+    pub fn canopy(&self, canopy_depth: u32) -> Vec<Node> {
+        let canopy_depth = canopy_depth.min(self.depth);
+        (2..1u64 << (canopy_depth + 1))
+            .map(|heap_index| self.nodes.get(&heap_index).copied().unwrap_or([0; 32]))
+            .collect()
+    }
+
+    /// Applies `changelog` if it is the next sequence of the tree, holds it if it is ahead and
+    /// ignores it if it was already applied.
+    ///
+    /// The changelog emitted when the tree is initialized has sequence 0 and only writes empty
+    /// nodes, so trees can be replayed starting from either sequence 0 or 1.
+    pub fn apply(
+        &mut self,
+        changelog: &ChangeLogEventV1,
+    ) -> Result<ReplayStatus, BlockbusterError> {
+        if changelog.path.len() != self.depth as usize + 1 {
+            return Err(BlockbusterError::ChangeLogDepthMismatch {
+                tree: changelog.id,
+                expected: self.depth as usize + 1,
+                actual: changelog.path.len(),
+            });
+        }
+        self.leaf_heap_index(changelog.index)?;
+
+        if changelog.seq <= self.seq {
+            return Ok(ReplayStatus::Stale);
+        }
+        self.pending.insert(
+            changelog.seq,
+            PendingChange {
+                index: changelog.index,
+                path: changelog.path.iter().map(|node| node.node).collect(),
+            },
+        );
+        if changelog.seq > self.seq + 1 {
+            return Ok(ReplayStatus::Pending);
+        }
+
+        while let Some(change) = self.pending.remove(&(self.seq + 1)) {
+            self.write_path(&change);
+            self.seq += 1;
+        }
+        Ok(ReplayStatus::Applied)
+    }
+
+    fn write_path(&mut self, change: &PendingChange) {
+        let mut heap_index = (1u64 << self.depth) + u64::from(change.index);
+        for node in change.path.iter() {
+            self.nodes.insert(heap_index, *node);
+            heap_index >>= 1;
+        }
+        self.leaf_count = self.leaf_count.max(u64::from(change.index) + 1);
+    }
+
+    fn node(&self, heap_index: u64, level: u32) -> Node {
+        self.nodes
+            .get(&heap_index)
+            .copied()
+            .unwrap_or(self.empty_nodes[level as usize])
+    }
+
+    fn leaf_heap_index(&self, index: u32) -> Result<u64, BlockbusterError> {
+        if u64::from(index) >= 1u64 << self.depth {
+            return Err(BlockbusterError::LeafIndexOutOfRange {
+                index,
+                depth: self.depth,
+            });
+        }
+        Ok((1u64 << self.depth) + u64::from(index))
+    }
+}
+
+/// Replays the changelogs of any number of trees, creating each tree from its first changelog.
+#[derive(Default)]
+pub struct TreeReplayer {
+    trees: HashMap<Pubkey, ReplayedTree>,
+}
+
+impl TreeReplayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn apply(
+        &mut self,
+        changelog: &ChangeLogEventV1,
+    ) -> Result<ReplayStatus, BlockbusterError> {
+        // The path holds every node from the leaf to the root.
+        let depth = changelog.path.len().saturating_sub(1);
+        if depth > MAX_DEPTH as usize {
+            return Err(BlockbusterError::ChangeLogDepthMismatch {
+                tree: changelog.id,
+                expected: MAX_DEPTH as usize + 1,
+                actual: changelog.path.len(),
+            });
+        }
+        let tree = match self.trees.entry(changelog.id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(ReplayedTree::new(changelog.id, depth as u32)?),
+        };
+        tree.apply(changelog)
+    }
+
+    pub fn tree(&self, id: &Pubkey) -> Option<&ReplayedTree> {
+        self.trees.get(id)
+    }
+
+    pub fn trees(&self) -> impl Iterator<Item = &ReplayedTree> {
+        self.trees.values()
+    }
+}
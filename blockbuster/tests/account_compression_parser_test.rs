@@ -0,0 +1,376 @@
+use anchor_lang::InstructionData;
+#[cfg(test)]
+use blockbuster::{
+    error::BlockbusterError,
+    noop::{mpl_account_compression_id, mpl_noop_id, NoopProgram},
+    program_handler::ProgramParser,
+    programs::{
+        account_compression::{
+            AccountCompressionInstruction, AccountCompressionParser, ConcurrentMerkleTreeAccount,
+            InstructionName, MerkleTreeHeaderVersion, Payload,
+        },
+        ProgramParseResult,
+    },
+};
+use helpers::*;
+use solana_sdk::pubkey::Pubkey;
+use spl_account_compression::{
+    events::{AccountCompressionEvent, ChangeLogEvent},
+    instruction::{
+        Append, CloseEmptyTree, InitEmptyMerkleTree, InsertOrAppend, ReplaceLeaf,
+        TransferAuthority, VerifyLeaf,
+    },
+    state::PathNode,
+};
+
+mod helpers;
+
+#[test]
+fn test_setup() {
+    let subject = AccountCompressionParser {};
+    assert_eq!(subject.key(), spl_account_compression::id());
+    assert_eq!(
+        subject.keys(),
+        vec![spl_account_compression::id(), mpl_account_compression_id()]
+    );
+    assert!(subject.key_match(&spl_account_compression::id()));
+    assert!(subject.key_match(&mpl_account_compression_id()));
+    assert!(subject.handles_instructions());
+}
+
+#[test]
+fn test_append() {
+    let subject = AccountCompressionParser {};
+
+    let accounts = random_list_of(3, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let ix_data = Append { leaf: [7; 32] }.data();
+
+    let changelog = AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+        accounts[0],
+        vec![PathNode {
+            node: [7; 32],
+            index: 16,
+        }],
+        3,
+        0,
+    ));
+
+    let ix_b = build_bundle_from_events(
+        spl_account_compression::id(),
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        vec![changelog],
+    );
+
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::AccountCompression(ac) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(ac.instruction, InstructionName::Append);
+    assert_eq!(
+        ac.payload,
+        Some(Payload::Append {
+            merkle_tree: accounts[0],
+            authority: accounts[1],
+            leaf: [7; 32],
+        })
+    );
+    let changelog = ac.tree_update.as_ref().unwrap();
+    assert_eq!(changelog.id, accounts[0]);
+    assert_eq!(changelog.seq, 3);
+    assert_eq!(ac.noop_program, Some(NoopProgram::Spl));
+}
+
+fn check_instruction(
+    accounts: &[Pubkey],
+    ix_data: &[u8],
+    events: Vec<AccountCompressionEvent>,
+    check: impl FnOnce(&AccountCompressionInstruction),
+) {
+    let subject = AccountCompressionParser {};
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+    let ix_b = build_bundle_from_events(
+        spl_account_compression::id(),
+        accounts,
+        &fb_account_indexes,
+        ix_data,
+        events,
+    );
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::AccountCompression(ac) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    check(ac);
+}
+
+fn changelog_event(tree: Pubkey, leaf: [u8; 32], index: u32, seq: u64) -> AccountCompressionEvent {
+    AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+        tree,
+        vec![PathNode {
+            node: leaf,
+            index: (1 << 3) + index,
+        }],
+        seq,
+        index,
+    ))
+}
+
+#[test]
+fn test_init_empty_merkle_tree() {
+    let accounts = random_list_of(3, |_i| random_pubkey());
+    let ix_data = InitEmptyMerkleTree {
+        max_depth: 14,
+        max_buffer_size: 64,
+    }
+    .data();
+
+    check_instruction(
+        &accounts,
+        &ix_data,
+        vec![changelog_event(accounts[0], [0; 32], 0, 0)],
+        |ac| {
+            assert_eq!(ac.instruction, InstructionName::InitEmptyMerkleTree);
+            assert_eq!(
+                ac.payload,
+                Some(Payload::InitEmptyMerkleTree {
+                    merkle_tree: accounts[0],
+                    authority: accounts[1],
+                    max_depth: 14,
+                    max_buffer_size: 64,
+                })
+            );
+            assert_eq!(ac.tree_update.as_ref().map(|cl| cl.seq), Some(0));
+        },
+    );
+}
+
+#[test]
+fn test_replace_leaf() {
+    let accounts = random_list_of(3, |_i| random_pubkey());
+    let ix_data = ReplaceLeaf {
+        root: [1; 32],
+        previous_leaf: [2; 32],
+        new_leaf: [3; 32],
+        index: 5,
+    }
+    .data();
+
+    check_instruction(
+        &accounts,
+        &ix_data,
+        vec![changelog_event(accounts[0], [3; 32], 5, 9)],
+        |ac| {
+            assert_eq!(ac.instruction, InstructionName::ReplaceLeaf);
+            assert_eq!(
+                ac.payload,
+                Some(Payload::ReplaceLeaf {
+                    merkle_tree: accounts[0],
+                    authority: accounts[1],
+                    root: [1; 32],
+                    previous_leaf: [2; 32],
+                    new_leaf: [3; 32],
+                    index: 5,
+                })
+            );
+            let changelog = ac.tree_update.as_ref().unwrap();
+            assert_eq!(changelog.seq, 9);
+            assert_eq!(changelog.index, 5);
+        },
+    );
+}
+
+#[test]
+fn test_insert_or_append() {
+    let accounts = random_list_of(3, |_i| random_pubkey());
+    let ix_data = InsertOrAppend {
+        root: [1; 32],
+        leaf: [4; 32],
+        index: 2,
+    }
+    .data();
+
+    check_instruction(
+        &accounts,
+        &ix_data,
+        vec![changelog_event(accounts[0], [4; 32], 2, 4)],
+        |ac| {
+            assert_eq!(ac.instruction, InstructionName::InsertOrAppend);
+            assert_eq!(
+                ac.payload,
+                Some(Payload::InsertOrAppend {
+                    merkle_tree: accounts[0],
+                    authority: accounts[1],
+                    root: [1; 32],
+                    leaf: [4; 32],
+                    index: 2,
+                })
+            );
+            assert_eq!(ac.tree_update.as_ref().map(|cl| cl.seq), Some(4));
+        },
+    );
+}
+
+#[test]
+fn test_verify_leaf() {
+    let accounts = random_list_of(1, |_i| random_pubkey());
+    let ix_data = VerifyLeaf {
+        root: [1; 32],
+        leaf: [2; 32],
+        index: 3,
+    }
+    .data();
+
+    check_instruction(&accounts, &ix_data, vec![], |ac| {
+        assert_eq!(ac.instruction, InstructionName::VerifyLeaf);
+        assert_eq!(
+            ac.payload,
+            Some(Payload::VerifyLeaf {
+                merkle_tree: accounts[0],
+                root: [1; 32],
+                leaf: [2; 32],
+                index: 3,
+            })
+        );
+        assert!(ac.tree_update.is_none());
+        assert!(ac.noop_program.is_none());
+    });
+}
+
+#[test]
+fn test_transfer_authority() {
+    let accounts = random_list_of(2, |_i| random_pubkey());
+    let new_authority = random_pubkey();
+    let ix_data = TransferAuthority { new_authority }.data();
+
+    check_instruction(&accounts, &ix_data, vec![], |ac| {
+        assert_eq!(ac.instruction, InstructionName::TransferAuthority);
+        assert_eq!(
+            ac.payload,
+            Some(Payload::TransferAuthority {
+                merkle_tree: accounts[0],
+                authority: accounts[1],
+                new_authority,
+            })
+        );
+        assert!(ac.tree_update.is_none());
+    });
+}
+
+#[test]
+fn test_close_empty_tree() {
+    let accounts = random_list_of(3, |_i| random_pubkey());
+    let ix_data = CloseEmptyTree {}.data();
+
+    check_instruction(&accounts, &ix_data, vec![], |ac| {
+        assert_eq!(ac.instruction, InstructionName::CloseEmptyTree);
+        assert_eq!(
+            ac.payload,
+            Some(Payload::CloseEmptyTree {
+                merkle_tree: accounts[0],
+                authority: accounts[1],
+                recipient: accounts[2],
+            })
+        );
+        assert!(ac.tree_update.is_none());
+    });
+}
+
+#[test]
+fn test_append_mpl_programs() {
+    let subject = AccountCompressionParser {};
+
+    let accounts = random_list_of(3, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let ix_data = Append { leaf: [7; 32] }.data();
+
+    let changelog = AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+        accounts[0],
+        vec![PathNode {
+            node: [7; 32],
+            index: 16,
+        }],
+        3,
+        0,
+    ));
+
+    let ix_b = build_bundle_from_noop_events(
+        mpl_account_compression_id(),
+        mpl_noop_id(),
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        vec![changelog],
+    );
+
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::AccountCompression(ac) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(ac.instruction, InstructionName::Append);
+    assert_eq!(ac.tree_update.as_ref().map(|cl| cl.seq), Some(3));
+    assert_eq!(ac.noop_program, Some(NoopProgram::Mpl));
+}
+
+#[test]
+fn test_merkle_tree_account() {
+    let subject = AccountCompressionParser {};
+    let authority = random_pubkey();
+
+    let mut data = vec![1, 0];
+    data.extend(2u32.to_le_bytes());
+    data.extend(3u32.to_le_bytes());
+    data.extend(authority.to_bytes());
+    data.extend(42u64.to_le_bytes());
+    data.extend([0; 6]);
+    data.extend(5u64.to_le_bytes());
+    data.extend(1u64.to_le_bytes());
+    data.extend(2u64.to_le_bytes());
+    for root in [[1; 32], [2; 32]] {
+        data.extend(root);
+        data.extend([[9; 32]; 3].concat());
+        data.extend(4u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+    }
+    data.extend([[3; 32], [4; 32], [5; 32]].concat());
+    data.extend([6; 32]);
+    data.extend(4u32.to_le_bytes());
+    data.extend(0u32.to_le_bytes());
+    data.extend([[7; 32], [8; 32]].concat());
+
+    let result = subject.handle_account(&data).unwrap();
+    let ProgramParseResult::ConcurrentMerkleTree(tree) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(tree.header.version, MerkleTreeHeaderVersion::V1);
+    assert_eq!(tree.header.max_buffer_size, 2);
+    assert_eq!(tree.header.max_depth, 3);
+    assert_eq!(tree.header.authority, authority);
+    assert_eq!(tree.header.creation_slot, 42);
+    assert_eq!(tree.sequence_number, 5);
+    assert_eq!(tree.root(), Some([2; 32]));
+    assert_eq!(tree.change_logs[0].path, vec![[9; 32]; 3]);
+    assert_eq!(tree.rightmost_proof.proof, vec![[3; 32], [4; 32], [5; 32]]);
+    assert_eq!(tree.rightmost_proof.leaf, [6; 32]);
+    assert_eq!(tree.rightmost_proof.index, 4);
+    assert_eq!(tree.canopy, vec![[7; 32], [8; 32]]);
+
+    assert!(matches!(
+        ConcurrentMerkleTreeAccount::from_account_data(&data[..100]),
+        Err(BlockbusterError::InvalidDataLength)
+    ));
+
+    let mut unknown_version = data.clone();
+    unknown_version[1] = 1;
+    assert!(matches!(
+        ConcurrentMerkleTreeAccount::from_account_data(&unknown_version),
+        Err(BlockbusterError::InvalidAccountType)
+    ));
+    assert!(matches!(
+        ConcurrentMerkleTreeAccount::from_account_data(&vec![0; data.len()]),
+        Err(BlockbusterError::UninitializedAccount)
+    ));
+}
@@ -1,17 +1,41 @@
 #[cfg(test)]
 use blockbuster::{
+    error::BlockbusterError,
     program_handler::ProgramParser,
-    programs::{bubblegum::BubblegumParser, ProgramParseResult},
+    programs::{
+        bubblegum::{
+            accounts::InstructionAccounts,
+            apply_update_args, get_asset_id, hash_creators, hash_metadata,
+            layout::LegacyCreateTreeInstructionArgs,
+            v2::{
+                AssetDataSchema, LeafInstructionV2Args, MetadataArgsV2,
+                UpdateMetadataV2InstructionArgs,
+            },
+            BubblegumAccount, BubblegumParser, InstructionName, LeafSchema, LeafSchemaEvent,
+            Payload, TreeConfig, Version, Voucher, NON_TRANSFERABLE_FLAG,
+        },
+        ProgramParseResult,
+    },
 };
+use borsh::BorshSerialize;
 use flatbuffers::FlatBufferBuilder;
 use helpers::*;
 use mpl_bubblegum::{
-    instructions::{MintV1InstructionArgs, TransferInstructionArgs},
-    types::{BubblegumEventType, Creator, LeafSchema, MetadataArgs, TokenProgramVersion, Version},
-    LeafSchemaEvent,
+    instructions::{
+        BurnInstructionArgs, CreateTreeConfigInstructionArgs, DelegateInstructionArgs,
+        MintV1InstructionArgs, RedeemInstructionArgs, SetDecompressibleStateInstructionArgs,
+        TransferInstructionArgs,
+    },
+    types::{
+        BubblegumEventType, Creator, DecompressibleState, MetadataArgs, TokenProgramVersion,
+        TokenStandard, UpdateArgs,
+    },
 };
+use solana_sdk::pubkey::Pubkey;
 use spl_account_compression::{
-    events::{AccountCompressionEvent, ChangeLogEvent},
+    events::{
+        AccountCompressionEvent, ApplicationDataEvent, ApplicationDataEventV1, ChangeLogEvent,
+    },
     state::PathNode,
 };
 
@@ -85,6 +109,8 @@ fn test_mint() {
         leaf_hash: [0; 32],
     };
 
+    let leaf_hash = lse.schema.hash();
+
     let cs = ChangeLogEvent::new(
         random_pubkey(),
         vec![PathNode {
@@ -117,13 +143,16 @@ fn test_mint() {
 
     if let ProgramParseResult::Bubblegum(b) = result.unwrap().result_type() {
         let matched = match b.instruction {
-            mpl_bubblegum::InstructionName::MintV1 => Ok(()),
+            InstructionName::MintV1 => Ok(()),
             _ => Err(()),
         };
         assert!(matched.is_ok());
         assert!(b.payload.is_some());
         assert!(b.leaf_update.is_some());
         assert!(b.tree_update.is_some());
+        assert_eq!(b.leaf_hash, Some(leaf_hash));
+        let tree = b.tree_update.as_ref().unwrap().id;
+        assert_eq!(b.asset_id, Some(get_asset_id(&tree, 0)));
     } else {
         panic!("Unexpected ProgramParseResult variant");
     }
@@ -208,9 +237,16 @@ fn test_basic_success_parsing() {
     let result = subject.handle_instruction(&ix_b);
 
     if let ProgramParseResult::Bubblegum(b) = result.unwrap().result_type() {
-        assert!(b.payload.is_none());
+        match &b.accounts {
+            Some(InstructionAccounts::Transfer(transfer)) => {
+                assert_eq!(transfer.tree_config, accounts[0]);
+                assert_eq!(transfer.merkle_tree, accounts[4]);
+                assert_eq!(transfer.system_program, accounts[7]);
+            }
+            _ => panic!("Expected Transfer accounts"),
+        }
         let matched = match b.instruction {
-            mpl_bubblegum::InstructionName::Transfer => Ok(()),
+            InstructionName::Transfer => Ok(()),
             _ => Err(()),
         };
         assert!(matched.is_ok());
@@ -220,3 +256,1172 @@ fn test_basic_success_parsing() {
         panic!("Unexpected ProgramParseResult variant");
     }
 }
+
+// Parses an instruction without noop events and passes its payload to `check`.
+fn check_payload(accounts: &[Pubkey], ix_data: &[u8], check: impl FnOnce(&Payload)) {
+    let subject = BubblegumParser {};
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+    let ix_b = build_bubblegum_bundle_from_events(accounts, &fb_account_indexes, ix_data, vec![]);
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::Bubblegum(b) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    check(b.payload.as_ref().expect("Expected a payload"));
+}
+
+#[test]
+fn test_transfer_payload() {
+    let accounts = random_list_of(8, |_i| random_pubkey());
+    let mut ix_data = vec![163, 52, 200, 231, 140, 3, 69, 186];
+    ix_data.extend(
+        TransferInstructionArgs {
+            root: [1; 32],
+            data_hash: [2; 32],
+            creator_hash: [3; 32],
+            nonce: 4,
+            index: 5,
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::Transfer {
+                    tree_id: accounts[4],
+                    leaf_owner: accounts[1],
+                    leaf_delegate: accounts[2],
+                    new_leaf_owner: accounts[3],
+                    root: [1; 32],
+                    data_hash: [2; 32],
+                    creator_hash: [3; 32],
+                    nonce: 4,
+                    index: 5,
+                }
+        );
+    });
+}
+
+#[test]
+fn test_delegate_payload() {
+    let accounts = random_list_of(8, |_i| random_pubkey());
+    let mut ix_data = vec![90, 147, 75, 178, 85, 88, 4, 137];
+    ix_data.extend(
+        DelegateInstructionArgs {
+            root: [1; 32],
+            data_hash: [2; 32],
+            creator_hash: [3; 32],
+            nonce: 4,
+            index: 5,
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::Delegate {
+                    tree_id: accounts[4],
+                    leaf_owner: accounts[1],
+                    previous_leaf_delegate: accounts[2],
+                    new_leaf_delegate: accounts[3],
+                    root: [1; 32],
+                    data_hash: [2; 32],
+                    creator_hash: [3; 32],
+                    nonce: 4,
+                    index: 5,
+                }
+        );
+    });
+}
+
+#[test]
+fn test_burn_payload() {
+    let accounts = random_list_of(7, |_i| random_pubkey());
+    let mut ix_data = vec![116, 110, 29, 56, 107, 219, 42, 93];
+    ix_data.extend(
+        BurnInstructionArgs {
+            root: [1; 32],
+            data_hash: [2; 32],
+            creator_hash: [3; 32],
+            nonce: 4,
+            index: 5,
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::Burn {
+                    tree_id: accounts[3],
+                    leaf_owner: accounts[1],
+                    leaf_delegate: accounts[2],
+                    root: [1; 32],
+                    data_hash: [2; 32],
+                    creator_hash: [3; 32],
+                    nonce: 4,
+                    index: 5,
+                }
+        );
+    });
+}
+
+#[test]
+fn test_redeem_payload() {
+    let accounts = random_list_of(8, |_i| random_pubkey());
+    let mut ix_data = vec![184, 12, 86, 149, 70, 196, 97, 225];
+    ix_data.extend(
+        RedeemInstructionArgs {
+            root: [1; 32],
+            data_hash: [2; 32],
+            creator_hash: [3; 32],
+            nonce: 4,
+            index: 5,
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::Redeem {
+                    tree_id: accounts[3],
+                    leaf_owner: accounts[1],
+                    leaf_delegate: accounts[2],
+                    voucher: accounts[4],
+                    root: [1; 32],
+                    data_hash: [2; 32],
+                    creator_hash: [3; 32],
+                    nonce: 4,
+                    index: 5,
+                }
+        );
+    });
+}
+
+#[test]
+fn test_create_tree_payload() {
+    let accounts = random_list_of(7, |_i| random_pubkey());
+    let mut ix_data = vec![165, 83, 136, 142, 89, 202, 47, 220];
+    ix_data.extend(
+        CreateTreeConfigInstructionArgs {
+            max_depth: 14,
+            max_buffer_size: 64,
+            public: Some(true),
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::CreateTree {
+                    tree_id: accounts[1],
+                    tree_creator: accounts[3],
+                    payer: accounts[2],
+                    max_depth: 14,
+                    max_buffer_size: 64,
+                    public: Some(true),
+                }
+        );
+    });
+}
+
+#[test]
+fn test_set_tree_delegate_payload() {
+    let accounts = random_list_of(5, |_i| random_pubkey());
+    let ix_data = vec![253, 118, 66, 37, 190, 49, 154, 102];
+
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::SetTreeDelegate {
+                    tree_id: accounts[3],
+                    tree_creator: accounts[1],
+                    new_tree_delegate: accounts[2],
+                }
+        );
+    });
+}
+
+#[test]
+fn test_set_decompressible_state_payload() {
+    let accounts = random_list_of(2, |_i| random_pubkey());
+    let mut ix_data = vec![82, 104, 152, 6, 149, 111, 100, 13];
+    ix_data.extend(
+        SetDecompressibleStateInstructionArgs {
+            decompressable_state: DecompressibleState::Enabled,
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::SetDecompressibleState {
+                    tree_config: accounts[0],
+                    tree_creator: accounts[1],
+                    decompressable_state: DecompressibleState::Enabled,
+                }
+        );
+    });
+}
+
+#[test]
+fn test_transfer_v2_parsing() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(11, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    // `transfer_v2` anchor discriminator followed by the instruction arguments.
+    let mut ix_data = vec![119, 40, 6, 235, 234, 221, 248, 49];
+    LeafInstructionV2Args {
+        root: [1; 32],
+        data_hash: [2; 32],
+        creator_hash: [3; 32],
+        asset_data_hash: None,
+        flags: Some(NON_TRANSFERABLE_FLAG),
+        nonce: 7,
+        index: 7,
+    }
+    .serialize(&mut ix_data)
+    .unwrap();
+
+    let schema = LeafSchema::V2 {
+        id: random_pubkey(),
+        owner: accounts[5],
+        delegate: accounts[5],
+        nonce: 7,
+        data_hash: [2; 32],
+        creator_hash: [3; 32],
+        collection_hash: [4; 32],
+        asset_data_hash: [5; 32],
+        flags: NON_TRANSFERABLE_FLAG,
+    };
+    let leaf_hash = schema.hash();
+    let lse = LeafSchemaEvent::new(Version::V2, schema, leaf_hash);
+
+    let cs = ChangeLogEvent::new(
+        accounts[6],
+        vec![PathNode {
+            node: leaf_hash,
+            index: 7,
+        }],
+        0,
+        7,
+    );
+    let cs_event = AccountCompressionEvent::ChangeLog(cs);
+
+    let mut fbb1 = FlatBufferBuilder::new();
+    let mut fbb2 = FlatBufferBuilder::new();
+    let mut fbb3 = FlatBufferBuilder::new();
+    let mut fbb4 = FlatBufferBuilder::new();
+
+    let ix_b = build_bubblegum_bundle(
+        &mut fbb1,
+        &mut fbb2,
+        &mut fbb3,
+        &mut fbb4,
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        lse,
+        cs_event,
+    );
+    let result = subject.handle_instruction(&ix_b);
+
+    if let ProgramParseResult::Bubblegum(b) = result.unwrap().result_type() {
+        assert_eq!(b.instruction, InstructionName::TransferV2);
+        match &b.payload {
+            Some(Payload::TransferV2 {
+                tree_id,
+                new_leaf_owner,
+                flags,
+                nonce,
+                ..
+            }) => {
+                assert_eq!(*tree_id, accounts[6]);
+                assert_eq!(*new_leaf_owner, accounts[5]);
+                assert_eq!(*flags, Some(NON_TRANSFERABLE_FLAG));
+                assert_eq!(*nonce, 7);
+            }
+            _ => panic!("Expected a TransferV2 payload"),
+        }
+        let leaf = b.leaf_update.as_ref().unwrap();
+        assert_eq!(leaf.version, Version::V2);
+        assert!(leaf.schema.is_non_transferable());
+        assert!(!leaf.schema.is_frozen());
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_mint_v2_fixture() {
+    let accounts = random_list_of(13, |_i| random_pubkey());
+    // `mint_v2` instruction data laid out byte by byte from the Bubblegum IDL, independently of
+    // the `v2` argument structs.
+    let mut ix_data: Vec<u8> = vec![120, 121, 23, 146, 173, 110, 199, 205];
+    // name: "Leaf"
+    ix_data.extend([4, 0, 0, 0, b'L', b'e', b'a', b'f']);
+    // symbol: "LF"
+    ix_data.extend([2, 0, 0, 0, b'L', b'F']);
+    // uri: "ar://x"
+    ix_data.extend([6, 0, 0, 0, b'a', b'r', b':', b'/', b'/', b'x']);
+    // seller_fee_basis_points: 500, primary_sale_happened: false, is_mutable: true
+    ix_data.extend([244, 1, 0, 1]);
+    // token_standard: Some(NonFungible)
+    ix_data.extend([1, 0]);
+    // creators: [{ address: [7; 32], verified: false, share: 100 }]
+    ix_data.extend([1, 0, 0, 0]);
+    ix_data.extend([7; 32]);
+    ix_data.extend([0, 100]);
+    // collection: Some([9; 32])
+    ix_data.push(1);
+    ix_data.extend([9; 32]);
+    // asset_data: Some([1, 2, 3]), asset_data_schema: Some(Binary)
+    ix_data.extend([1, 3, 0, 0, 0, 1, 2, 3]);
+    ix_data.extend([1, 0]);
+
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::MintV2 {
+                    args: MetadataArgsV2 {
+                        name: "Leaf".to_string(),
+                        symbol: "LF".to_string(),
+                        uri: "ar://x".to_string(),
+                        seller_fee_basis_points: 500,
+                        primary_sale_happened: false,
+                        is_mutable: true,
+                        token_standard: Some(TokenStandard::NonFungible),
+                        creators: vec![Creator {
+                            address: Pubkey::new_from_array([7; 32]),
+                            verified: false,
+                            share: 100,
+                        }],
+                        collection: Some(Pubkey::new_from_array([9; 32])),
+                    },
+                    asset_data: Some(vec![1, 2, 3]),
+                    asset_data_schema: Some(AssetDataSchema::Binary),
+                    authority: accounts[2],
+                    tree_id: accounts[6],
+                    leaf_owner: accounts[4],
+                    leaf_delegate: accounts[5],
+                }
+        );
+    });
+}
+
+#[test]
+fn test_update_metadata_v2_payload() {
+    let accounts = random_list_of(10, |_i| random_pubkey());
+    let current_metadata = MetadataArgsV2 {
+        name: "Old".to_string(),
+        symbol: "OLD".to_string(),
+        uri: "ar://old".to_string(),
+        seller_fee_basis_points: 100,
+        primary_sale_happened: false,
+        is_mutable: true,
+        token_standard: Some(TokenStandard::NonFungible),
+        creators: vec![],
+        collection: None,
+    };
+    let update_args = UpdateArgs {
+        name: Some("New".to_string()),
+        symbol: None,
+        uri: Some("ar://new".to_string()),
+        creators: None,
+        seller_fee_basis_points: None,
+        primary_sale_happened: Some(true),
+        is_mutable: None,
+    };
+    let mut ix_data = vec![43, 103, 89, 42, 121, 242, 62, 72];
+    UpdateMetadataV2InstructionArgs {
+        root: [1; 32],
+        current_metadata: current_metadata.clone(),
+        update_args: update_args.clone(),
+        asset_data_hash: None,
+        flags: None,
+        nonce: 2,
+        index: 2,
+    }
+    .serialize(&mut ix_data)
+    .unwrap();
+
+    let updated_metadata = MetadataArgsV2 {
+        name: "New".to_string(),
+        uri: "ar://new".to_string(),
+        primary_sale_happened: true,
+        ..current_metadata.clone()
+    };
+    check_payload(&accounts, &ix_data, |payload| {
+        assert!(
+            *payload
+                == Payload::UpdateMetadataV2 {
+                    current_metadata,
+                    update_args,
+                    updated_metadata,
+                    tree_id: accounts[5],
+                }
+        );
+    });
+}
+
+#[test]
+fn test_multiple_noop_events_are_kept_in_order() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(8, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let mut accounts_iter = accounts.iter();
+    let ix = mpl_bubblegum::instructions::Transfer {
+        tree_config: *accounts_iter.next().unwrap(),
+        leaf_owner: (*accounts_iter.next().unwrap(), true),
+        leaf_delegate: (*accounts_iter.next().unwrap(), false),
+        new_leaf_owner: *accounts_iter.next().unwrap(),
+        merkle_tree: *accounts_iter.next().unwrap(),
+        log_wrapper: *accounts_iter.next().unwrap(),
+        compression_program: *accounts_iter.next().unwrap(),
+        system_program: *accounts_iter.next().unwrap(),
+    };
+    let ix_data = ix
+        .instruction(TransferInstructionArgs {
+            root: [0; 32],
+            data_hash: [0; 32],
+            creator_hash: [0; 32],
+            nonce: 3,
+            index: 3,
+        })
+        .data;
+
+    let schema = LeafSchema::V1 {
+        id: random_pubkey(),
+        owner: accounts[3],
+        delegate: accounts[3],
+        nonce: 3,
+        data_hash: [0; 32],
+        creator_hash: [0; 32],
+    };
+    let leaf_hash = schema.hash();
+    let lse = LeafSchemaEvent::new(Version::V1, schema, leaf_hash);
+
+    let first_changelog = AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+        accounts[4],
+        vec![PathNode {
+            node: [9; 32],
+            index: 2,
+        }],
+        1,
+        2,
+    ));
+    let second_changelog = AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+        accounts[4],
+        vec![PathNode {
+            node: leaf_hash,
+            index: 3,
+        }],
+        2,
+        3,
+    ));
+
+    let ix_b = build_bubblegum_bundle_from_events(
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        vec![
+            first_changelog,
+            leaf_schema_event_to_compression_event(&lse),
+            second_changelog,
+        ],
+    );
+    let result = subject.handle_instruction(&ix_b);
+
+    if let ProgramParseResult::Bubblegum(b) = result.unwrap().result_type() {
+        let seqs = b
+            .tree_updates
+            .iter()
+            .map(|update| (update.inner_ix_index, update.event.seq))
+            .collect::<Vec<_>>();
+        assert_eq!(seqs, vec![(0, 1), (2, 2)]);
+        assert_eq!(b.tree_update.as_ref().map(|cl| cl.seq), Some(2));
+
+        assert_eq!(b.leaf_updates.len(), 1);
+        assert_eq!(b.leaf_updates[0].inner_ix_index, 1);
+        assert_eq!(b.leaf_updates[0].event, lse);
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_leaf_hash_mismatch() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(8, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let mut accounts_iter = accounts.iter();
+    let ix = mpl_bubblegum::instructions::Transfer {
+        tree_config: *accounts_iter.next().unwrap(),
+        leaf_owner: (*accounts_iter.next().unwrap(), true),
+        leaf_delegate: (*accounts_iter.next().unwrap(), false),
+        new_leaf_owner: *accounts_iter.next().unwrap(),
+        merkle_tree: *accounts_iter.next().unwrap(),
+        log_wrapper: *accounts_iter.next().unwrap(),
+        compression_program: *accounts_iter.next().unwrap(),
+        system_program: *accounts_iter.next().unwrap(),
+    };
+    let ix_data = ix
+        .instruction(TransferInstructionArgs {
+            root: [0; 32],
+            data_hash: [0; 32],
+            creator_hash: [0; 32],
+            nonce: 0,
+            index: 0,
+        })
+        .data;
+
+    let schema = LeafSchema::V1 {
+        id: random_pubkey(),
+        owner: accounts[3],
+        delegate: accounts[3],
+        nonce: 0,
+        data_hash: [0; 32],
+        creator_hash: [0; 32],
+    };
+    let leaf_hash = schema.hash();
+    let lse = LeafSchemaEvent::new(Version::V1, schema, leaf_hash);
+
+    let changelog = AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+        accounts[4],
+        vec![PathNode {
+            node: [1; 32],
+            index: 0,
+        }],
+        5,
+        0,
+    ));
+
+    let ix_b = build_bubblegum_bundle_from_events(
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        vec![leaf_schema_event_to_compression_event(&lse), changelog],
+    );
+
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::Bubblegum(b) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert!(matches!(b.payload, Some(Payload::Transfer { .. })));
+
+    let mismatches = b.leaf_hash_mismatches();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].inner_ix_index, 0);
+    match &mismatches[0].error {
+        BlockbusterError::LeafHashMismatch {
+            tree,
+            seq,
+            computed,
+            changelog,
+        } => {
+            assert_eq!(*tree, accounts[4]);
+            assert_eq!(*seq, 5);
+            assert_eq!(*computed, leaf_hash);
+            assert_eq!(*changelog, [1; 32]);
+        }
+        _ => panic!("Expected a leaf hash mismatch"),
+    }
+    assert!(matches!(
+        b.verify_leaf_hashes(),
+        Err(BlockbusterError::LeafHashMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_decompress_derived_addresses() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(13, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let metadata = MetadataArgs {
+        name: "test".to_string(),
+        symbol: "test".to_string(),
+        uri: "www.solana.pos".to_owned(),
+        seller_fee_basis_points: 0,
+        primary_sale_happened: false,
+        is_mutable: false,
+        edition_nonce: None,
+        token_standard: None,
+        token_program_version: TokenProgramVersion::Original,
+        collection: None,
+        uses: None,
+        creators: vec![],
+    };
+    let mut ix_data = vec![54, 85, 76, 70, 228, 250, 164, 81];
+    ix_data.extend(metadata.try_to_vec().unwrap());
+
+    let ix_b = build_bubblegum_bundle_from_events(&accounts, &fb_account_indexes, &ix_data, vec![]);
+
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::Bubblegum(b) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    let mint = accounts[3];
+    match &b.payload {
+        Some(Payload::Decompress {
+            args,
+            voucher,
+            leaf_owner,
+            token_account,
+            mint: payload_mint,
+            metadata,
+            master_edition,
+        }) => {
+            assert_eq!(args.name, "test");
+            assert_eq!(*voucher, accounts[0]);
+            assert_eq!(*leaf_owner, accounts[1]);
+            assert_eq!(*token_account, accounts[2]);
+            assert_eq!(*payload_mint, mint);
+            assert_eq!(
+                *metadata,
+                mpl_token_metadata::accounts::Metadata::find_pda(&mint).0
+            );
+            assert_eq!(
+                *master_edition,
+                mpl_token_metadata::accounts::MasterEdition::find_pda(&mint).0
+            );
+        }
+        _ => panic!("Expected a Decompress payload"),
+    }
+    assert_eq!(b.asset_id, Some(mint));
+}
+
+#[test]
+fn test_compress() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(14, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+    let ix_data = vec![82, 193, 176, 117, 176, 21, 115, 253];
+
+    let schema = LeafSchema::V1 {
+        id: get_asset_id(&accounts[3], 7),
+        owner: accounts[1],
+        delegate: accounts[2],
+        nonce: 7,
+        data_hash: [2; 32],
+        creator_hash: [3; 32],
+    };
+    let lse = LeafSchemaEvent::new(Version::V1, schema.clone(), schema.hash());
+    let changelog = AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+        accounts[3],
+        vec![PathNode {
+            node: schema.hash(),
+            index: 0,
+        }],
+        8,
+        7,
+    ));
+
+    let ix_b = build_bubblegum_bundle_from_events(
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        vec![leaf_schema_event_to_compression_event(&lse), changelog],
+    );
+
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::Bubblegum(b) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(b.instruction, InstructionName::Compress);
+    assert!(
+        b.payload
+            == Some(Payload::Compress {
+                tree_id: accounts[3],
+                leaf_owner: accounts[1],
+                leaf_delegate: accounts[2],
+                token_account: accounts[4],
+                mint: accounts[5],
+                metadata: accounts[6],
+                master_edition: accounts[7],
+                leaf: Some(schema),
+            })
+    );
+    assert_eq!(b.asset_id, Some(get_asset_id(&accounts[3], 7)));
+}
+
+#[test]
+fn test_legacy_create_tree_layout() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(7, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    // max_depth 14 and max_buffer_size 64, as written before the `public` argument existed.
+    let legacy_ix_data = [
+        165, 83, 136, 142, 89, 202, 47, 220, 14, 0, 0, 0, 64, 0, 0, 0,
+    ];
+    // The same arguments followed by `public: Some(true)`.
+    let current_ix_data = [
+        165, 83, 136, 142, 89, 202, 47, 220, 14, 0, 0, 0, 64, 0, 0, 0, 1, 1,
+    ];
+    assert_eq!(
+        LegacyCreateTreeInstructionArgs {
+            max_depth: 14,
+            max_buffer_size: 64,
+        }
+        .try_to_vec()
+        .unwrap(),
+        legacy_ix_data[8..]
+    );
+
+    let cases = [
+        (&legacy_ix_data[..], None),
+        (&current_ix_data[..], Some(true)),
+    ];
+    for (ix_data, expected_public) in cases {
+        let ix_b =
+            build_bubblegum_bundle_from_events(&accounts, &fb_account_indexes, ix_data, vec![]);
+        let result = subject.handle_instruction(&ix_b).unwrap();
+        let ProgramParseResult::Bubblegum(b) = result.result_type() else {
+            panic!("Unexpected ProgramParseResult variant");
+        };
+        match &b.payload {
+            Some(Payload::CreateTree {
+                tree_id,
+                max_depth,
+                max_buffer_size,
+                public,
+                ..
+            }) => {
+                assert_eq!(*tree_id, accounts[1]);
+                assert_eq!(*max_depth, 14);
+                assert_eq!(*max_buffer_size, 64);
+                assert_eq!(*public, expected_public);
+            }
+            _ => panic!("Expected a CreateTree payload"),
+        }
+    }
+}
+
+#[test]
+fn test_mint_v1_fixture() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(9, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+    let creator = random_pubkey();
+
+    // `mint_v1` data laid out field by field, independently of the `MetadataArgs` encoder.
+    let mut ix_data = vec![145, 98, 192, 118, 184, 147, 118, 104];
+    ix_data.extend([4, 0, 0, 0]);
+    ix_data.extend(b"name");
+    ix_data.extend([3, 0, 0, 0]);
+    ix_data.extend(b"SYM");
+    ix_data.extend([3, 0, 0, 0]);
+    ix_data.extend(b"uri");
+    // Seller fee basis points, primary sale happened and is mutable.
+    ix_data.extend([0xf4, 0x01, 0, 1]);
+    // Edition nonce, token standard, collection and uses.
+    ix_data.extend([1, 255, 1, 0, 0, 0]);
+    // Token program version and creators.
+    ix_data.extend([0, 1, 0, 0, 0]);
+    ix_data.extend(creator.to_bytes());
+    ix_data.extend([1, 100]);
+
+    let ix_b = build_bubblegum_bundle_from_events(&accounts, &fb_account_indexes, &ix_data, vec![]);
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::Bubblegum(b) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    match &b.payload {
+        Some(Payload::MintV1 {
+            args,
+            authority,
+            tree_id,
+        }) => {
+            assert_eq!(*authority, accounts[0]);
+            assert_eq!(*tree_id, accounts[3]);
+            assert_eq!(
+                *args,
+                MetadataArgs {
+                    name: "name".to_string(),
+                    symbol: "SYM".to_string(),
+                    uri: "uri".to_string(),
+                    seller_fee_basis_points: 500,
+                    primary_sale_happened: false,
+                    is_mutable: true,
+                    edition_nonce: Some(255),
+                    token_standard: Some(TokenStandard::NonFungible),
+                    collection: None,
+                    uses: None,
+                    token_program_version: TokenProgramVersion::Original,
+                    creators: vec![Creator {
+                        address: creator,
+                        verified: true,
+                        share: 100,
+                    }],
+                }
+            );
+        }
+        _ => panic!("Expected a MintV1 payload"),
+    }
+}
+
+#[test]
+fn test_missing_instruction_account() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(4, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let ix_data = mpl_bubblegum::instructions::Transfer {
+        tree_config: random_pubkey(),
+        leaf_owner: (random_pubkey(), true),
+        leaf_delegate: (random_pubkey(), false),
+        new_leaf_owner: random_pubkey(),
+        merkle_tree: random_pubkey(),
+        log_wrapper: random_pubkey(),
+        compression_program: random_pubkey(),
+        system_program: random_pubkey(),
+    }
+    .instruction(TransferInstructionArgs {
+        root: [0; 32],
+        data_hash: [0; 32],
+        creator_hash: [0; 32],
+        nonce: 0,
+        index: 0,
+    })
+    .data;
+
+    let ix_b = build_bubblegum_bundle_from_events(&accounts, &fb_account_indexes, &ix_data, vec![]);
+
+    match subject.handle_instruction(&ix_b) {
+        Err(BlockbusterError::MissingInstructionAccount { accounts, account }) => {
+            assert_eq!(accounts, "TransferAccounts");
+            assert_eq!(account, "merkle_tree");
+        }
+        _ => panic!("Expected a missing account error"),
+    }
+}
+
+#[test]
+fn test_partial_result_with_malformed_application_data() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(8, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let mut accounts_iter = accounts.iter();
+    let ix = mpl_bubblegum::instructions::Transfer {
+        tree_config: *accounts_iter.next().unwrap(),
+        leaf_owner: (*accounts_iter.next().unwrap(), true),
+        leaf_delegate: (*accounts_iter.next().unwrap(), false),
+        new_leaf_owner: *accounts_iter.next().unwrap(),
+        merkle_tree: *accounts_iter.next().unwrap(),
+        log_wrapper: *accounts_iter.next().unwrap(),
+        compression_program: *accounts_iter.next().unwrap(),
+        system_program: *accounts_iter.next().unwrap(),
+    };
+    let ix_data = ix
+        .instruction(TransferInstructionArgs {
+            root: [0; 32],
+            data_hash: [0; 32],
+            creator_hash: [0; 32],
+            nonce: 0,
+            index: 0,
+        })
+        .data;
+
+    let uninitialized = AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1(
+        ApplicationDataEventV1 {
+            application_data: BubblegumEventType::Uninitialized.try_to_vec().unwrap(),
+        },
+    ));
+    let changelog = AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+        accounts[4],
+        vec![PathNode {
+            node: [1; 32],
+            index: 0,
+        }],
+        5,
+        0,
+    ));
+
+    let ix_b = build_bubblegum_bundle_from_events(
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        vec![uninitialized, changelog],
+    );
+
+    assert!(matches!(
+        subject.handle_instruction(&ix_b),
+        Err(BlockbusterError::MissingBubblegumEventData)
+    ));
+
+    let (b_inst, diagnostics) = subject.handle_instruction_with_diagnostics(&ix_b).unwrap();
+    assert_eq!(b_inst.instruction, InstructionName::Transfer);
+    assert!(matches!(b_inst.payload, Some(Payload::Transfer { .. })));
+    assert!(b_inst.leaf_update.is_none());
+    assert_eq!(b_inst.tree_updates.len(), 1);
+    assert_eq!(b_inst.tree_updates[0].event.seq, 5);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].inner_ix_index, 0);
+    assert!(matches!(
+        diagnostics[0].error,
+        BlockbusterError::MissingBubblegumEventData
+    ));
+}
+
+#[test]
+fn test_verify_metadata_hashes() {
+    let subject = BubblegumParser {};
+
+    let accounts = random_list_of(9, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let metadata = MetadataArgs {
+        name: "test".to_string(),
+        symbol: "test".to_string(),
+        uri: "www.solana.pos".to_owned(),
+        seller_fee_basis_points: 100,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: None,
+        token_program_version: TokenProgramVersion::Original,
+        collection: None,
+        uses: None,
+        creators: vec![Creator {
+            address: random_pubkey(),
+            verified: false,
+            share: 100,
+        }],
+    };
+    let data_hash = hash_metadata(&metadata).unwrap();
+    let creator_hash = hash_creators(&metadata.creators);
+
+    let mut accounts_iter = accounts.iter();
+    let ix = mpl_bubblegum::instructions::MintV1 {
+        tree_config: *accounts_iter.next().unwrap(),
+        leaf_owner: *accounts_iter.next().unwrap(),
+        leaf_delegate: *accounts_iter.next().unwrap(),
+        merkle_tree: *accounts_iter.next().unwrap(),
+        payer: *accounts_iter.next().unwrap(),
+        tree_creator_or_delegate: *accounts_iter.next().unwrap(),
+        log_wrapper: *accounts_iter.next().unwrap(),
+        compression_program: *accounts_iter.next().unwrap(),
+        system_program: *accounts_iter.next().unwrap(),
+    };
+    let ix_data = ix.instruction(MintV1InstructionArgs { metadata }).data;
+
+    for (leaf_creator_hash, expect_ok) in [(creator_hash, true), ([0; 32], false)] {
+        let schema = LeafSchema::V1 {
+            id: random_pubkey(),
+            owner: accounts[1],
+            delegate: accounts[2],
+            nonce: 0,
+            data_hash,
+            creator_hash: leaf_creator_hash,
+        };
+        let leaf_hash = schema.hash();
+        let lse = LeafSchemaEvent::new(Version::V1, schema, leaf_hash);
+        let changelog = AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(
+            accounts[3],
+            vec![PathNode {
+                node: leaf_hash,
+                index: 0,
+            }],
+            1,
+            0,
+        ));
+
+        let ix_b = build_bubblegum_bundle_from_events(
+            &accounts,
+            &fb_account_indexes,
+            &ix_data,
+            vec![leaf_schema_event_to_compression_event(&lse), changelog],
+        );
+        let result = subject.handle_instruction(&ix_b).unwrap();
+
+        if let ProgramParseResult::Bubblegum(b) = result.result_type() {
+            match b.verify_metadata_hashes() {
+                Ok(()) => assert!(expect_ok),
+                Err(BlockbusterError::CreatorHashMismatch { computed, leaf }) => {
+                    assert!(!expect_ok);
+                    assert_eq!(computed, creator_hash);
+                    assert_eq!(leaf, [0; 32]);
+                }
+                Err(e) => panic!("Unexpected error {:?}", e),
+            }
+        } else {
+            panic!("Unexpected ProgramParseResult variant");
+        }
+    }
+}
+
+#[test]
+fn test_hash_known_answers() {
+    let creators = vec![Creator {
+        address: Pubkey::new_from_array([1; 32]),
+        verified: true,
+        share: 100,
+    }];
+    let metadata = MetadataArgs {
+        name: "test".to_string(),
+        symbol: "TST".to_string(),
+        uri: "ar://test".to_string(),
+        seller_fee_basis_points: 500,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: None,
+        collection: None,
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: creators.clone(),
+    };
+
+    // keccak256 of the empty input.
+    assert_eq!(
+        hash_creators(&[]),
+        [
+            197, 210, 70, 1, 134, 247, 35, 60, 146, 126, 125, 178, 220, 199, 3, 192, 229, 0, 182,
+            83, 202, 130, 39, 59, 123, 250, 216, 4, 93, 133, 164, 112
+        ]
+    );
+    assert_eq!(
+        hash_creators(&creators),
+        [
+            122, 202, 124, 216, 207, 138, 77, 78, 15, 160, 8, 104, 207, 88, 198, 209, 227, 159,
+            210, 199, 55, 137, 95, 106, 73, 247, 235, 59, 130, 28, 45, 53
+        ]
+    );
+    assert_eq!(
+        hash_metadata(&metadata).unwrap(),
+        [
+            101, 123, 9, 238, 127, 130, 189, 228, 59, 91, 167, 11, 121, 175, 218, 84, 174, 144,
+            115, 55, 157, 5, 169, 27, 225, 127, 128, 133, 37, 34, 197, 124
+        ]
+    );
+}
+
+#[test]
+fn test_apply_update_args() {
+    let current = MetadataArgs {
+        name: "before".to_string(),
+        symbol: "BFR".to_string(),
+        uri: "https://before".to_owned(),
+        seller_fee_basis_points: 100,
+        primary_sale_happened: true,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: None,
+        token_program_version: TokenProgramVersion::Original,
+        collection: None,
+        uses: None,
+        creators: vec![Creator {
+            address: random_pubkey(),
+            verified: true,
+            share: 100,
+        }],
+    };
+
+    let new_creators = vec![
+        current.creators[0].clone(),
+        Creator {
+            address: random_pubkey(),
+            verified: false,
+            share: 0,
+        },
+    ];
+    let update_args = UpdateArgs {
+        name: Some("after".to_string()),
+        symbol: None,
+        uri: Some("https://after".to_owned()),
+        creators: Some(new_creators.clone()),
+        seller_fee_basis_points: Some(250),
+        primary_sale_happened: Some(false),
+        is_mutable: Some(false),
+    };
+
+    let updated = apply_update_args(&current, &update_args);
+    assert_eq!(updated.name, "after");
+    assert_eq!(updated.symbol, "BFR");
+    assert_eq!(updated.uri, "https://after");
+    assert_eq!(updated.creators, new_creators);
+    assert_eq!(updated.seller_fee_basis_points, 250);
+    // Primary sale cannot be reverted once it happened.
+    assert!(updated.primary_sale_happened);
+    assert!(!updated.is_mutable);
+}
+
+#[test]
+fn test_account_parsing() {
+    let subject = BubblegumParser {};
+    assert!(subject.handles_account_updates());
+
+    let tree_config = TreeConfig {
+        discriminator: [122, 245, 175, 248, 171, 34, 0, 207],
+        tree_creator: random_pubkey(),
+        tree_delegate: random_pubkey(),
+        total_mint_capacity: 1 << 14,
+        num_minted: 12,
+        is_public: false,
+        is_decompressible: DecompressibleState::Disabled,
+    };
+    let mut data = tree_config.try_to_vec().unwrap();
+    // Accounts are allocated with trailing padding.
+    data.resize(TreeConfig::LEN, 0);
+
+    let result = subject.handle_account(&data).unwrap();
+    match result.result_type() {
+        ProgramParseResult::BubblegumAccount(BubblegumAccount::TreeConfig(parsed)) => {
+            assert_eq!(*parsed, tree_config);
+        }
+        _ => panic!("Expected a TreeConfig account"),
+    }
+
+    let voucher = Voucher {
+        discriminator: [191, 204, 149, 234, 213, 165, 13, 65],
+        leaf_schema: LeafSchema::V1 {
+            id: random_pubkey(),
+            owner: random_pubkey(),
+            delegate: random_pubkey(),
+            nonce: 42,
+            data_hash: [1; 32],
+            creator_hash: [2; 32],
+        },
+        index: 42,
+        merkle_tree: random_pubkey(),
+    };
+    let data = voucher.try_to_vec().unwrap();
+
+    let result = subject.handle_account(&data).unwrap();
+    match result.result_type() {
+        ProgramParseResult::BubblegumAccount(BubblegumAccount::Voucher(parsed)) => {
+            assert_eq!(*parsed, voucher);
+        }
+        _ => panic!("Expected a Voucher account"),
+    }
+
+    assert!(matches!(
+        subject.handle_account(&[0; 16]),
+        Err(BlockbusterError::UnknownAccountDiscriminator)
+    ));
+}
@@ -3,10 +3,10 @@
 use blockbuster::{
     error::BlockbusterError,
     instruction::{InstructionBundle, IxPair},
+    programs::bubblegum::LeafSchemaEvent,
 };
 use borsh::ser::BorshSerialize;
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
-use mpl_bubblegum::LeafSchemaEvent;
 use plerkle_serialization::{
     root_as_account_info, root_as_compiled_instruction,
     serializer::seralize_encoded_transaction_with_status, AccountInfo, AccountInfoArgs,
@@ -324,6 +324,83 @@ pub fn build_bubblegum_bundle<'a>(
     }
 }
 
+/// Builds a Bubblegum bundle whose inner instructions are one noop call per event, in order.
+pub fn build_bubblegum_bundle_from_events<'a>(
+    accounts: &'a [Pubkey],
+    account_indexes: &[u8],
+    ix_data: &[u8],
+    events: Vec<AccountCompressionEvent>,
+) -> InstructionBundle<'a> {
+    build_bundle_from_events(
+        mpl_bubblegum::ID,
+        accounts,
+        account_indexes,
+        ix_data,
+        events,
+    )
+}
+
+/// Builds a bundle for `program` whose inner instructions are one noop call per event, in order.
+pub fn build_bundle_from_events<'a>(
+    program: Pubkey,
+    accounts: &'a [Pubkey],
+    account_indexes: &[u8],
+    ix_data: &[u8],
+    events: Vec<AccountCompressionEvent>,
+) -> InstructionBundle<'a> {
+    build_bundle_from_noop_events(
+        program,
+        spl_noop::id(),
+        accounts,
+        account_indexes,
+        ix_data,
+        events,
+    )
+}
+
+/// Same as `build_bundle_from_events`, with the events logged through `noop_program`.
+pub fn build_bundle_from_noop_events<'a>(
+    program: Pubkey,
+    noop_program: Pubkey,
+    accounts: &'a [Pubkey],
+    account_indexes: &[u8],
+    ix_data: &[u8],
+    events: Vec<AccountCompressionEvent>,
+) -> InstructionBundle<'a> {
+    let outer_ix = CompiledInstruction {
+        program_id_index: 0,
+        accounts: account_indexes.to_vec(),
+        data: ix_data.to_vec(),
+    };
+
+    let inner_ix = events
+        .into_iter()
+        .map(|event| {
+            let ix = CompiledInstruction {
+                program_id_index: 0,
+                accounts: account_indexes.to_vec(),
+                data: spl_noop::instruction(event.try_to_vec().unwrap()).data,
+            };
+            (noop_program, &*Box::leak(Box::new(ix)))
+        })
+        .collect::<Vec<IxPair>>();
+
+    // `Box::leak` is ok for tests
+    InstructionBundle {
+        program,
+        inner_ix: Some(Box::leak(Box::new(inner_ix))),
+        keys: accounts,
+        instruction: Some(Box::leak(Box::new(outer_ix))),
+        ..Default::default()
+    }
+}
+
+pub fn leaf_schema_event_to_compression_event(lse: &LeafSchemaEvent) -> AccountCompressionEvent {
+    AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1(ApplicationDataEventV1 {
+        application_data: lse.try_to_vec().unwrap(),
+    }))
+}
+
 pub fn parse_fb(
     tx_info: &TransactionInfo,
 ) -> (
@@ -3,16 +3,20 @@ mod helpers;
 use anchor_lang::AnchorDeserialize;
 use blockbuster::{
     instruction::{order_instructions, InstructionBundle},
+    noop::{mpl_noop_id, noop_events, NoopProgram},
     program_handler::ProgramParser,
     programs::{
         bubblegum::{BubblegumParser, LeafSchemaEvent, Payload},
         ProgramParseResult,
     },
 };
+use borsh::BorshSerialize;
 use flatbuffers::FlatBufferBuilder;
 use helpers::*;
 use plerkle_serialization::root_as_transaction_info;
 use rand::prelude::IteratorRandom;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::{InnerInstruction, InnerInstructions};
 use spl_account_compression::events::{
     AccountCompressionEvent::{self},
     ApplicationDataEvent, ApplicationDataEventV1, ChangeLogEvent, ChangeLogEventV1,
@@ -225,3 +229,142 @@ fn test_double_tree() {
     });
     assert_eq!(count, 2);
 }
+
+// Runs `noop_events` over a single outer instruction of `account_keys[0]` and checks that each
+// event points at its noop instruction in the `order_instructions` output.
+fn noop_event_positions(
+    programs: &HashSet<Pubkey>,
+    account_keys: &[Pubkey],
+    inner_instructions: Vec<InnerInstruction>,
+) -> Vec<(Pubkey, usize, usize, NoopProgram, Vec<u8>)> {
+    let message_instructions = vec![CompiledInstruction {
+        program_id_index: 0,
+        accounts: vec![],
+        data: vec![],
+    }];
+    let meta_inner_instructions = vec![InnerInstructions {
+        index: 0,
+        instructions: inner_instructions,
+    }];
+    let ordered_ixs = order_instructions(
+        programs,
+        account_keys,
+        &message_instructions,
+        &meta_inner_instructions,
+    );
+
+    noop_events(
+        programs,
+        account_keys,
+        &message_instructions,
+        &meta_inner_instructions,
+    )
+    .into_iter()
+    .map(|noop_event| {
+        let ((program, _), inner_ixs) = &ordered_ixs[noop_event.instruction_index];
+        assert_eq!(*program, noop_event.program);
+        let (noop_program_id, noop_ix) = inner_ixs.as_ref().unwrap()[noop_event.inner_ix_index];
+        assert_eq!(noop_program_id, noop_event.noop_program.id());
+        assert_eq!(noop_ix.data, noop_event.event.try_to_vec().unwrap());
+
+        let AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1(app_data)) =
+            noop_event.event
+        else {
+            panic!("Unexpected event");
+        };
+        (
+            noop_event.program,
+            noop_event.instruction_index,
+            noop_event.inner_ix_index,
+            noop_event.noop_program,
+            app_data.application_data,
+        )
+    })
+    .collect()
+}
+
+fn inner_instruction(
+    program_id_index: u8,
+    data: Vec<u8>,
+    stack_height: Option<u32>,
+) -> InnerInstruction {
+    InnerInstruction {
+        instruction: CompiledInstruction {
+            program_id_index,
+            accounts: vec![],
+            data,
+        },
+        stack_height,
+    }
+}
+
+fn app_data_event(application_data: Vec<u8>) -> Vec<u8> {
+    AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1(ApplicationDataEventV1 {
+        application_data,
+    }))
+    .try_to_vec()
+    .unwrap()
+}
+
+#[test]
+fn test_noop_events() {
+    let outer = random_program();
+    let custom = random_program();
+    let account_keys = vec![outer, custom, spl_noop::id(), mpl_noop_id()];
+    let programs = HashSet::from([outer, custom]);
+
+    // Without stack heights, the events logged after the call to `custom` are reported once,
+    // under `custom`, in execution order.
+    let events = noop_event_positions(
+        &programs,
+        &account_keys,
+        vec![
+            inner_instruction(2, app_data_event(vec![1]), None),
+            inner_instruction(1, vec![], None),
+            inner_instruction(2, app_data_event(vec![2]), None),
+            inner_instruction(3, app_data_event(vec![3]), None),
+            inner_instruction(2, vec![], None),
+        ],
+    );
+    assert_eq!(
+        events,
+        vec![
+            (outer, 1, 0, NoopProgram::Spl, vec![1]),
+            (custom, 0, 0, NoopProgram::Spl, vec![2]),
+            (custom, 0, 1, NoopProgram::Mpl, vec![3]),
+        ]
+    );
+}
+
+#[test]
+fn test_noop_events_with_stack_heights() {
+    let outer = random_program();
+    let custom = random_program();
+    let other = random_program();
+    let account_keys = vec![outer, custom, spl_noop::id(), mpl_noop_id(), other];
+    let programs = HashSet::from([outer, custom]);
+
+    // `custom` logs through an unknown program and directly, then `outer` logs after `custom`
+    // returned.
+    let events = noop_event_positions(
+        &programs,
+        &account_keys,
+        vec![
+            inner_instruction(2, app_data_event(vec![1]), Some(2)),
+            inner_instruction(1, vec![], Some(2)),
+            inner_instruction(4, vec![], Some(3)),
+            inner_instruction(2, app_data_event(vec![2]), Some(4)),
+            inner_instruction(3, app_data_event(vec![3]), Some(3)),
+            inner_instruction(2, app_data_event(vec![4]), Some(2)),
+        ],
+    );
+    assert_eq!(
+        events,
+        vec![
+            (outer, 1, 0, NoopProgram::Spl, vec![1]),
+            (custom, 0, 1, NoopProgram::Spl, vec![2]),
+            (custom, 0, 2, NoopProgram::Mpl, vec![3]),
+            (outer, 1, 5, NoopProgram::Spl, vec![4]),
+        ]
+    );
+}
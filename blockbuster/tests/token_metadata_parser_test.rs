@@ -0,0 +1,682 @@
+#[cfg(test)]
+use blockbuster::{
+    error::BlockbusterError,
+    program_handler::ProgramParser,
+    programs::{
+        token_metadata::{
+            accounts::InstructionAccounts, pda::TokenMetadataPda, token_metadata_id, FieldIssue,
+            InstructionName, LenientMetadata, MetadataField, MetadataFieldIssue, Payload,
+            Reservation, ReservationListV1, ReservationListV2, ReservationV1, SanitizationIssue,
+            SanitizationWarning, TokenMetadataAccountData, TokenMetadataParser,
+        },
+        ProgramParseResult,
+    },
+};
+use borsh::BorshSerialize;
+use helpers::*;
+use mpl_token_metadata::{
+    accounts::{
+        EditionMarker, EditionMarkerV2, HolderDelegateRecord, MasterEdition, Metadata,
+        MetadataDelegateRecord, TokenOwnedEscrow, TokenRecord,
+    },
+    instructions::CreateMetadataAccountV3InstructionArgs,
+    types::{
+        BurnArgs, CollectionDetailsToggle, CollectionToggle, CreateArgs, Creator, DataV2,
+        EscrowAuthority, Key, MintArgs, PrintSupply, RuleSetToggle, TokenDelegateRole,
+        TokenStandard, TokenState, TransferArgs, UpdateArgs, UsesToggle, VerificationArgs,
+    },
+};
+use solana_sdk::pubkey::Pubkey;
+
+mod helpers;
+
+#[test]
+fn test_setup() {
+    let subject = TokenMetadataParser {};
+    assert_eq!(subject.key(), token_metadata_id());
+    assert!(subject.key_match(&token_metadata_id()));
+    assert!(subject.handles_instructions());
+    assert!(subject.handles_account_updates());
+}
+
+#[test]
+fn test_create_metadata_account_v3() {
+    let subject = TokenMetadataParser {};
+
+    let accounts = random_list_of(6, |_i| random_pubkey());
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+    let args = CreateMetadataAccountV3InstructionArgs {
+        data: DataV2 {
+            name: "Name".to_string(),
+            symbol: "SYM".to_string(),
+            uri: "https://example.com".to_string(),
+            seller_fee_basis_points: 500,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        is_mutable: true,
+        collection_details: None,
+    };
+    let mut ix_data = vec![33];
+    ix_data.extend(args.try_to_vec().unwrap());
+
+    let ix_b = build_bundle_from_events(
+        token_metadata_id(),
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        vec![],
+    );
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::TokenMetadataInstruction(tm) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(tm.instruction, InstructionName::CreateMetadataAccountV3);
+    assert_eq!(tm.payload, Some(Payload::CreateMetadataAccountV3(args)));
+    let Some(InstructionAccounts::CreateMetadataAccountV3(named)) = tm.accounts else {
+        panic!("Unexpected accounts");
+    };
+    assert_eq!(named.metadata, accounts[0]);
+    assert_eq!(named.update_authority, accounts[4]);
+    assert_eq!(named.rent, None);
+
+    let ix_b = build_bundle_from_events(
+        token_metadata_id(),
+        &accounts[..3],
+        &fb_account_indexes[..3],
+        &ix_data,
+        vec![],
+    );
+    assert!(matches!(
+        subject.handle_instruction(&ix_b),
+        Err(BlockbusterError::MissingInstructionAccount {
+            accounts: "CreateMetadataAccountV3Accounts",
+            account: "payer",
+        })
+    ));
+}
+
+#[test]
+fn test_transfer() {
+    let subject = TokenMetadataParser {};
+
+    let mut accounts = random_list_of(15, |_i| random_pubkey());
+    // Optional accounts that are not provided are passed as the program ID.
+    accounts[6] = token_metadata_id();
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+    let args = TransferArgs::V1 {
+        amount: 1,
+        authorization_data: None,
+    };
+    let mut ix_data = vec![49];
+    ix_data.extend(args.try_to_vec().unwrap());
+
+    let ix_b = build_bundle_from_events(
+        token_metadata_id(),
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        vec![],
+    );
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::TokenMetadataInstruction(tm) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(tm.instruction, InstructionName::Transfer);
+    assert_eq!(tm.payload, Some(Payload::Transfer(args)));
+    let Some(InstructionAccounts::Transfer(named)) = tm.accounts else {
+        panic!("Unexpected accounts");
+    };
+    assert_eq!(named.destination_owner, accounts[3]);
+    assert_eq!(named.edition, None);
+    assert_eq!(named.token_record, Some(accounts[7]));
+    assert_eq!(named.authority, accounts[9]);
+    assert_eq!(named.authorization_rules, None);
+
+    // Instructions the parser does not know are reported without a payload.
+    let ix_b = build_bundle_from_events(
+        token_metadata_id(),
+        &accounts,
+        &fb_account_indexes,
+        &[1, 2, 3],
+        vec![],
+    );
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::TokenMetadataInstruction(tm) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(tm.instruction, InstructionName::Unknown);
+    assert!(tm.payload.is_none());
+    assert!(tm.accounts.is_none());
+}
+
+// Parses a Token Metadata instruction and returns its payload and named accounts.
+fn parse_instruction(
+    accounts: &[Pubkey],
+    ix_data: &[u8],
+) -> (Option<Payload>, Option<InstructionAccounts>) {
+    let subject = TokenMetadataParser {};
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+    let ix_b = build_bundle_from_events(
+        token_metadata_id(),
+        accounts,
+        &fb_account_indexes,
+        ix_data,
+        vec![],
+    );
+    let result = subject.handle_instruction(&ix_b).unwrap();
+    let ProgramParseResult::TokenMetadataInstruction(tm) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    (tm.payload.clone(), tm.accounts)
+}
+
+#[test]
+fn test_create() {
+    let mut accounts = random_list_of(9, |_i| random_pubkey());
+    accounts[1] = token_metadata_id();
+    accounts[8] = token_metadata_id();
+    let args = CreateArgs::V1 {
+        name: "Name".to_string(),
+        symbol: "SYM".to_string(),
+        uri: "https://example.com".to_string(),
+        seller_fee_basis_points: 500,
+        creators: None,
+        primary_sale_happened: false,
+        is_mutable: true,
+        token_standard: TokenStandard::ProgrammableNonFungible,
+        collection: None,
+        uses: None,
+        collection_details: None,
+        rule_set: Some(accounts[0]),
+        decimals: Some(0),
+        print_supply: Some(PrintSupply::Zero),
+    };
+    let mut ix_data = vec![42];
+    ix_data.extend(args.try_to_vec().unwrap());
+
+    let (payload, named) = parse_instruction(&accounts, &ix_data);
+    assert_eq!(payload, Some(Payload::Create(args)));
+    let Some(InstructionAccounts::Create(named)) = named else {
+        panic!("Unexpected accounts");
+    };
+    assert_eq!(named.metadata, accounts[0]);
+    assert_eq!(named.master_edition, None);
+    assert_eq!(named.mint, accounts[2]);
+    assert_eq!(named.update_authority, accounts[5]);
+    assert_eq!(named.spl_token_program, None);
+}
+
+#[test]
+fn test_update() {
+    let mut accounts = random_list_of(11, |_i| random_pubkey());
+    for optional in [1, 2, 5, 9, 10] {
+        accounts[optional] = token_metadata_id();
+    }
+    let args = UpdateArgs::AsUpdateAuthorityV2 {
+        new_update_authority: Some(accounts[0]),
+        data: None,
+        primary_sale_happened: Some(true),
+        is_mutable: None,
+        collection: CollectionToggle::Clear,
+        collection_details: CollectionDetailsToggle::None,
+        uses: UsesToggle::None,
+        rule_set: RuleSetToggle::None,
+        token_standard: Some(TokenStandard::NonFungible),
+        authorization_data: None,
+    };
+    let mut ix_data = vec![50];
+    ix_data.extend(args.try_to_vec().unwrap());
+
+    let (payload, named) = parse_instruction(&accounts, &ix_data);
+    assert_eq!(payload, Some(Payload::Update(args)));
+    let Some(InstructionAccounts::Update(named)) = named else {
+        panic!("Unexpected accounts");
+    };
+    assert_eq!(named.authority, accounts[0]);
+    assert_eq!(named.delegate_record, None);
+    assert_eq!(named.token, None);
+    assert_eq!(named.mint, accounts[3]);
+    assert_eq!(named.metadata, accounts[4]);
+    assert_eq!(named.edition, None);
+    assert_eq!(named.authorization_rules_program, None);
+    assert_eq!(named.authorization_rules, None);
+}
+
+#[test]
+fn test_mint() {
+    // The trailing optional accounts are left out of the instruction.
+    let accounts = random_list_of(13, |_i| random_pubkey());
+    let args = MintArgs::V1 {
+        amount: 1,
+        authorization_data: None,
+    };
+    let mut ix_data = vec![43];
+    ix_data.extend(args.try_to_vec().unwrap());
+
+    let (payload, named) = parse_instruction(&accounts, &ix_data);
+    assert_eq!(payload, Some(Payload::Mint(args)));
+    let Some(InstructionAccounts::Mint(named)) = named else {
+        panic!("Unexpected accounts");
+    };
+    assert_eq!(named.token, accounts[0]);
+    assert_eq!(named.token_owner, Some(accounts[1]));
+    assert_eq!(named.mint, accounts[5]);
+    assert_eq!(named.spl_ata_program, accounts[12]);
+    assert_eq!(named.authorization_rules_program, None);
+    assert_eq!(named.authorization_rules, None);
+}
+
+#[test]
+fn test_burn() {
+    let mut accounts = random_list_of(14, |_i| random_pubkey());
+    for optional in [1, 3, 6, 7, 8, 9, 10] {
+        accounts[optional] = token_metadata_id();
+    }
+    let args = BurnArgs::V1 { amount: 1 };
+    let mut ix_data = vec![41];
+    ix_data.extend(args.try_to_vec().unwrap());
+
+    let (payload, named) = parse_instruction(&accounts, &ix_data);
+    assert_eq!(payload, Some(Payload::Burn(args)));
+    let Some(InstructionAccounts::Burn(named)) = named else {
+        panic!("Unexpected accounts");
+    };
+    assert_eq!(named.authority, accounts[0]);
+    assert_eq!(named.collection_metadata, None);
+    assert_eq!(named.metadata, accounts[2]);
+    assert_eq!(named.edition, None);
+    assert_eq!(named.token, accounts[5]);
+    assert_eq!(named.master_edition, None);
+    assert_eq!(named.token_record, None);
+    assert_eq!(named.spl_token_program, accounts[13]);
+}
+
+#[test]
+fn test_verify() {
+    let mut accounts = random_list_of(8, |_i| random_pubkey());
+    accounts[1] = token_metadata_id();
+
+    for args in [VerificationArgs::CreatorV1, VerificationArgs::CollectionV1] {
+        let mut ix_data = vec![52];
+        ix_data.extend(args.try_to_vec().unwrap());
+
+        let (payload, named) = parse_instruction(&accounts, &ix_data);
+        assert_eq!(payload, Some(Payload::Verify(args)));
+        let Some(InstructionAccounts::Verify(named)) = named else {
+            panic!("Unexpected accounts");
+        };
+        assert_eq!(named.authority, accounts[0]);
+        assert_eq!(named.delegate_record, None);
+        assert_eq!(named.metadata, accounts[2]);
+        assert_eq!(named.collection_mint, Some(accounts[3]));
+        assert_eq!(named.collection_master_edition, Some(accounts[5]));
+    }
+}
+
+#[test]
+fn test_remaining_account_keys() {
+    let subject = TokenMetadataParser {};
+
+    let edition_marker = EditionMarkerV2 {
+        key: Key::EditionMarkerV2,
+        ledger: vec![0b1010_0000, 1],
+    };
+    let result = subject
+        .handle_account(&edition_marker.try_to_vec().unwrap())
+        .unwrap();
+    let ProgramParseResult::TokenMetadata(state) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(state.key, Key::EditionMarkerV2);
+    let TokenMetadataAccountData::EditionMarkerV2(parsed) = &state.data else {
+        panic!("Expected an EditionMarkerV2 account");
+    };
+    assert_eq!(*parsed, edition_marker);
+
+    let master_edition = random_pubkey();
+    let address = random_pubkey();
+    // Laid out as the program writes it: key, master edition, supply snapshot, reservations,
+    // total and current reservation spots.
+    let mut data = vec![5];
+    data.extend(master_edition.to_bytes());
+    data.extend([1, 3, 0, 0, 0, 0, 0, 0, 0]);
+    data.extend([1, 0, 0, 0]);
+    data.extend(address.to_bytes());
+    data.extend([1, 0, 0, 0, 0, 0, 0, 0]);
+    data.extend([2, 0, 0, 0, 0, 0, 0, 0]);
+    data.extend([2, 0, 0, 0, 0, 0, 0, 0]);
+    data.extend([1, 0, 0, 0, 0, 0, 0, 0]);
+    // Accounts are allocated with trailing padding.
+    data.resize(data.len() + 64, 0);
+    let result = subject.handle_account(&data).unwrap();
+    let ProgramParseResult::TokenMetadata(state) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    let TokenMetadataAccountData::ReservationListV2(parsed) = &state.data else {
+        panic!("Expected a ReservationListV2 account");
+    };
+    assert_eq!(
+        *parsed,
+        ReservationListV2 {
+            key: Key::ReservationListV2,
+            master_edition,
+            supply_snapshot: Some(3),
+            reservations: vec![Reservation {
+                address,
+                spots_remaining: 1,
+                total_spots: 2,
+            }],
+            total_reservation_spots: 2,
+            current_reservation_spots: 1,
+        }
+    );
+
+    let mut data = ReservationListV1 {
+        key: Key::ReservationListV1,
+        master_edition,
+        supply_snapshot: None,
+        reservations: vec![ReservationV1 {
+            address,
+            spots_remaining: 1,
+            total_spots: 2,
+        }],
+    }
+    .try_to_vec()
+    .unwrap();
+    data.resize(data.len() + 64, 0);
+    check_account(&data, |account| {
+        let TokenMetadataAccountData::ReservationListV1(parsed) = account else {
+            panic!("Expected a ReservationListV1 account");
+        };
+        assert_eq!(parsed.master_edition, master_edition);
+        assert_eq!(parsed.reservations[0].address, address);
+    });
+
+    let metadata_delegate = MetadataDelegateRecord {
+        key: Key::MetadataDelegate,
+        bump: 255,
+        mint: random_pubkey(),
+        delegate: random_pubkey(),
+        update_authority: random_pubkey(),
+    };
+    check_account(&metadata_delegate.try_to_vec().unwrap(), |account| {
+        let TokenMetadataAccountData::MetadataDelegate(parsed) = account else {
+            panic!("Expected a MetadataDelegate account");
+        };
+        assert_eq!(*parsed, metadata_delegate);
+    });
+
+    let holder_delegate = HolderDelegateRecord {
+        key: Key::HolderDelegate,
+        bump: 254,
+        mint: random_pubkey(),
+        delegate: random_pubkey(),
+        update_authority: random_pubkey(),
+    };
+    check_account(&holder_delegate.try_to_vec().unwrap(), |account| {
+        let TokenMetadataAccountData::HolderDelegate(parsed) = account else {
+            panic!("Expected a HolderDelegate account");
+        };
+        assert_eq!(*parsed, holder_delegate);
+    });
+
+    let escrow = TokenOwnedEscrow {
+        key: Key::TokenOwnedEscrow,
+        base_token: random_pubkey(),
+        authority: EscrowAuthority::Creator(random_pubkey()),
+        bump: 253,
+    };
+    check_account(&escrow.try_to_vec().unwrap(), |account| {
+        let TokenMetadataAccountData::TokenOwnedEscrow(parsed) = account else {
+            panic!("Expected a TokenOwnedEscrow account");
+        };
+        assert_eq!(*parsed, escrow);
+    });
+}
+
+#[test]
+fn test_token_record_account() {
+    let token_record = TokenRecord {
+        key: Key::TokenRecord,
+        bump: 255,
+        state: TokenState::Locked,
+        rule_set_revision: Some(2),
+        delegate: Some(random_pubkey()),
+        delegate_role: Some(TokenDelegateRole::Utility),
+        locked_transfer: Some(random_pubkey()),
+    };
+    let data = token_record.try_to_vec().unwrap();
+    assert_eq!(data.len(), TokenRecord::LEN);
+    check_account(&data, |account| {
+        let TokenMetadataAccountData::TokenRecord(parsed) = account else {
+            panic!("Expected a TokenRecord account");
+        };
+        assert_eq!(*parsed, token_record);
+    });
+
+    // Records are allocated at full size, unset options are followed by zeroes.
+    let unlocked = TokenRecord {
+        locked_transfer: None,
+        ..token_record.clone()
+    };
+    let mut data = unlocked.try_to_vec().unwrap();
+    data.resize(TokenRecord::LEN, 0);
+    check_account(&data, |account| {
+        let TokenMetadataAccountData::TokenRecord(parsed) = account else {
+            panic!("Expected a TokenRecord account");
+        };
+        assert_eq!(*parsed, unlocked);
+    });
+
+    // Records written before `locked_transfer` existed are 47 bytes long, and fill them
+    // completely when every option is set.
+    let data = unlocked.try_to_vec().unwrap();
+    let legacy = &data[..data.len() - 1];
+    assert_eq!(legacy.len(), 47);
+    check_account(legacy, |account| {
+        let TokenMetadataAccountData::TokenRecord(parsed) = account else {
+            panic!("Expected a TokenRecord account");
+        };
+        assert_eq!(*parsed, unlocked);
+    });
+}
+
+fn check_account(data: &[u8], check: impl FnOnce(&TokenMetadataAccountData)) {
+    let subject = TokenMetadataParser {};
+    let result = subject.handle_account(data).unwrap();
+    let ProgramParseResult::TokenMetadata(state) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    check(&state.data);
+}
+
+fn test_metadata() -> Metadata {
+    Metadata {
+        key: Key::MetadataV1,
+        update_authority: random_pubkey(),
+        mint: random_pubkey(),
+        name: "Name".to_string(),
+        symbol: "SYM".to_string(),
+        uri: "https://example.com".to_string(),
+        seller_fee_basis_points: 500,
+        creators: Some(vec![
+            Creator {
+                address: random_pubkey(),
+                verified: true,
+                share: 60,
+            },
+            Creator {
+                address: random_pubkey(),
+                verified: false,
+                share: 40,
+            },
+        ]),
+        primary_sale_happened: true,
+        is_mutable: true,
+        edition_nonce: Some(255),
+        token_standard: None,
+        collection: None,
+        uses: None,
+        collection_details: None,
+        programmable_config: None,
+    }
+}
+
+#[test]
+fn test_lenient_metadata() {
+    let metadata = test_metadata();
+    let data = metadata.try_to_vec().unwrap();
+
+    let decoded = LenientMetadata::from_account_data(&data).unwrap();
+    assert!(decoded.is_complete());
+    assert_eq!(decoded.metadata, metadata);
+
+    // Creators cut short in the middle of the second one.
+    let creators_end = data.len() - 2 - 2 - 5;
+    let decoded = LenientMetadata::from_account_data(&data[..creators_end - 10]).unwrap();
+    assert_eq!(decoded.metadata.name, metadata.name);
+    assert_eq!(decoded.metadata.uri, metadata.uri);
+    assert_eq!(
+        decoded.metadata.creators.as_deref(),
+        metadata.creators.as_ref().map(|creators| &creators[..1])
+    );
+    assert!(!decoded.metadata.is_mutable);
+    let issues = decoded
+        .issues
+        .iter()
+        .map(|issue| (issue.field, issue.issue))
+        .collect::<Vec<_>>();
+    assert_eq!(issues[0], (MetadataField::Creators, FieldIssue::Repaired));
+    assert!(issues[1..]
+        .iter()
+        .all(|(_, issue)| *issue == FieldIssue::Missing));
+    assert_eq!(issues.len(), 9);
+
+    // Garbage in place of `is_mutable` and the edition nonce.
+    let mut garbage = data.clone();
+    garbage[creators_end + 1] = 7;
+    garbage[creators_end + 2] = 9;
+    let decoded = LenientMetadata::from_account_data(&garbage).unwrap();
+    assert_eq!(decoded.metadata.creators, metadata.creators);
+    assert!(decoded.metadata.is_mutable);
+    assert_eq!(decoded.metadata.edition_nonce, None);
+    assert_eq!(
+        decoded.issues[..2],
+        [
+            MetadataFieldIssue {
+                field: MetadataField::IsMutable,
+                issue: FieldIssue::Repaired,
+            },
+            MetadataFieldIssue {
+                field: MetadataField::EditionNonce,
+                issue: FieldIssue::Unreadable,
+            },
+        ]
+    );
+    assert_eq!(decoded.issues.len(), 7);
+
+    // The parser falls back to the lenient decoder and keeps the issues.
+    let subject = TokenMetadataParser {};
+    let result = subject.handle_account(&garbage).unwrap();
+    let ProgramParseResult::TokenMetadata(state) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert_eq!(state.key, Key::MetadataV1);
+    let TokenMetadataAccountData::PartialMetadataV1(partial) = &state.data else {
+        panic!("Expected a PartialMetadataV1 account");
+    };
+    assert_eq!(*partial, decoded);
+    assert_eq!(state.metadata(), Some(&decoded.metadata));
+
+    let result = subject.handle_account(&data).unwrap();
+    let ProgramParseResult::TokenMetadata(state) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    assert!(matches!(
+        state.data,
+        TokenMetadataAccountData::MetadataV1(_)
+    ));
+
+    assert!(LenientMetadata::from_account_data(&data[..40]).is_err());
+    assert!(subject.handle_account(&data[..40]).is_err());
+}
+
+#[test]
+fn test_normalized_metadata() {
+    let subject = TokenMetadataParser {};
+
+    let mut metadata = test_metadata();
+    metadata.name = format!("Name{}", "\0".repeat(28));
+    metadata.symbol = " SYM\u{7}".to_string();
+    metadata.uri = format!("HTTPS://example.com/0.json{}", "\0".repeat(100));
+    let result = subject
+        .handle_account(&metadata.try_to_vec().unwrap())
+        .unwrap();
+    let ProgramParseResult::TokenMetadata(state) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    let normalized = state.normalized_metadata().unwrap();
+    assert_eq!(normalized.name, "Name");
+    assert_eq!(normalized.symbol, "SYM");
+    assert_eq!(normalized.uri, "HTTPS://example.com/0.json");
+    assert_eq!(normalized.uri_scheme.as_deref(), Some("https"));
+    assert_eq!(
+        normalized.warnings,
+        vec![
+            SanitizationWarning {
+                field: MetadataField::Symbol,
+                issue: SanitizationIssue::ControlCharacters,
+            },
+            SanitizationWarning {
+                field: MetadataField::Symbol,
+                issue: SanitizationIssue::Whitespace,
+            },
+        ]
+    );
+
+    metadata.symbol = "SYM".to_string();
+    metadata.uri = "example.com/0.json".to_string();
+    let normalized = LenientMetadata::from_account_data(&metadata.try_to_vec().unwrap())
+        .unwrap()
+        .normalized();
+    assert_eq!(normalized.uri_scheme, None);
+    assert_eq!(
+        normalized.warnings,
+        vec![SanitizationWarning {
+            field: MetadataField::Uri,
+            issue: SanitizationIssue::InvalidUriScheme,
+        }]
+    );
+}
+
+#[test]
+fn test_pdas() {
+    let subject = TokenMetadataParser {};
+
+    let metadata = test_metadata();
+    let mint = metadata.mint;
+    let (metadata_address, _) = TokenMetadataPda::Metadata { mint }.find_address();
+    assert_eq!(metadata_address, Metadata::find_pda(&mint).0);
+    let (edition_address, _) = TokenMetadataPda::Edition { mint }.find_address();
+    assert_eq!(edition_address, MasterEdition::find_pda(&mint).0);
+
+    let result = subject
+        .handle_account(&metadata.try_to_vec().unwrap())
+        .unwrap();
+    let ProgramParseResult::TokenMetadata(state) = result.result_type() else {
+        panic!("Unexpected ProgramParseResult variant");
+    };
+    let pda = state.pda().unwrap();
+    assert_eq!(pda, TokenMetadataPda::Metadata { mint });
+    assert!(state.verify_address(&metadata_address, &pda));
+    assert!(!state.verify_address(&edition_address, &pda));
+    assert!(!state.verify_address(&edition_address, &TokenMetadataPda::Edition { mint }));
+
+    // Edition markers group 248 editions per account.
+    let marker = |edition| TokenMetadataPda::EditionMarker { mint, edition }.find_address();
+    assert_eq!(marker(1), marker(247));
+    assert_ne!(marker(247), marker(248));
+    assert_eq!(marker(500), EditionMarker::find_pda(&mint, "2"));
+}
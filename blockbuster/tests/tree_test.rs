@@ -0,0 +1,201 @@
+use blockbuster::{
+    error::BlockbusterError,
+    tree::{
+        canopy_depth, compute_root, fill_proof_from_canopy, truncate_proof, update_proof,
+        verify_proof, ReplayStatus, ReplayedTree, SequenceGapTracker, TreeReplayer, MAX_DEPTH,
+    },
+};
+use helpers::*;
+use spl_account_compression::{
+    events::{ChangeLogEvent, ChangeLogEventV1},
+    state::PathNode,
+};
+use spl_concurrent_merkle_tree::concurrent_merkle_tree::ConcurrentMerkleTree;
+
+mod helpers;
+
+const DEPTH: usize = 3;
+
+// Builds the changelog event of the last modification of `tree`, as the program emits it.
+fn last_changelog(
+    id: solana_sdk::pubkey::Pubkey,
+    tree: &ConcurrentMerkleTree<DEPTH, 8>,
+) -> ChangeLogEventV1 {
+    let change_log = &tree.change_logs[tree.active_index as usize];
+    let index = change_log.index;
+    let path = change_log
+        .path
+        .iter()
+        .enumerate()
+        .map(|(level, node)| PathNode {
+            node: *node,
+            index: (1 << (DEPTH - level)) + (index >> level),
+        })
+        .chain(std::iter::once(PathNode {
+            node: change_log.root,
+            index: 1,
+        }))
+        .collect();
+    let ChangeLogEvent::V1(changelog) = ChangeLogEvent::new(id, path, tree.sequence_number, index);
+    changelog
+}
+
+#[test]
+fn test_replay_matches_concurrent_merkle_tree() {
+    let id = random_pubkey();
+    let mut tree = ConcurrentMerkleTree::<DEPTH, 8>::new();
+    tree.initialize().unwrap();
+
+    let mut replayer = TreeReplayer::new();
+    let mut changelogs = Vec::new();
+    for leaf in 1..=5u8 {
+        tree.append([leaf; 32]).unwrap();
+        let changelog = last_changelog(id, &tree);
+        assert_eq!(replayer.apply(&changelog).unwrap(), ReplayStatus::Applied);
+        changelogs.push(changelog);
+
+        let replayed = replayer.tree(&id).unwrap();
+        assert_eq!(replayed.root(), tree.get_root());
+    }
+
+    let replayed = replayer.tree(&id).unwrap();
+    assert_eq!(replayed.depth(), DEPTH as u32);
+    assert_eq!(replayed.seq(), 5);
+    assert_eq!(replayed.leaf_count(), 5);
+    for index in 0..8u32 {
+        let leaf = replayed.leaf(index).unwrap();
+        let proof = replayed.proof(index).unwrap();
+        assert_eq!(compute_root(&leaf, index, &proof), tree.get_root());
+    }
+    assert!(replayed.proof(8).is_err());
+
+    assert_eq!(replayer.apply(&changelogs[0]).unwrap(), ReplayStatus::Stale);
+
+    // Out of order changelogs are held until the missing sequences arrive.
+    let mut out_of_order = TreeReplayer::new();
+    for changelog in changelogs.iter().skip(1) {
+        assert_eq!(
+            out_of_order.apply(changelog).unwrap(),
+            ReplayStatus::Pending
+        );
+    }
+    assert_eq!(out_of_order.tree(&id).unwrap().pending(), 4);
+    assert_eq!(
+        out_of_order.apply(&changelogs[0]).unwrap(),
+        ReplayStatus::Applied
+    );
+    let replayed = out_of_order.tree(&id).unwrap();
+    assert_eq!(replayed.pending(), 0);
+    assert_eq!(replayed.root(), tree.get_root());
+}
+
+#[test]
+fn test_replayed_tree_depth() {
+    let id = random_pubkey();
+    let tree = ReplayedTree::new(id, MAX_DEPTH).unwrap();
+    assert_eq!(tree.depth(), MAX_DEPTH);
+    assert!(matches!(
+        ReplayedTree::new(id, MAX_DEPTH + 1),
+        Err(BlockbusterError::TreeDepthTooLarge(depth)) if depth == MAX_DEPTH + 1
+    ));
+}
+
+#[test]
+fn test_sequence_gaps() {
+    let tree = random_pubkey();
+    let other_tree = random_pubkey();
+    let mut tracker = SequenceGapTracker::new();
+
+    for seq in [0, 3, 1, 7, 2, 9, 8, 3] {
+        tracker.observe(tree, seq);
+    }
+    tracker.observe(other_tree, 2);
+
+    assert_eq!(tracker.gaps(&tree), vec![4..=6]);
+    assert_eq!(tracker.highest_contiguous(&tree), 3);
+    assert_eq!(tracker.highest_seen(&tree), Some(9));
+
+    assert_eq!(tracker.gaps(&other_tree), vec![1..=1]);
+    assert_eq!(tracker.highest_contiguous(&other_tree), 0);
+
+    for seq in 4..=6 {
+        tracker.observe(tree, seq);
+    }
+    assert!(tracker.gaps(&tree).is_empty());
+    assert_eq!(tracker.highest_contiguous(&tree), 9);
+
+    let unknown = random_pubkey();
+    assert!(tracker.gaps(&unknown).is_empty());
+    assert_eq!(tracker.highest_seen(&unknown), None);
+}
+
+#[test]
+fn test_proofs() {
+    let id = random_pubkey();
+    let mut tree = ConcurrentMerkleTree::<DEPTH, 8>::new();
+    tree.initialize().unwrap();
+    let mut replayer = TreeReplayer::new();
+    for leaf in 1..=3u8 {
+        tree.append([leaf; 32]).unwrap();
+        replayer.apply(&last_changelog(id, &tree)).unwrap();
+    }
+
+    let replayed = replayer.tree(&id).unwrap();
+    let mut proof = replayed.proof(1).unwrap();
+    assert!(verify_proof(&tree.get_root(), &[2; 32], 1, &proof));
+    assert!(!verify_proof(&tree.get_root(), &[2; 32], 0, &proof));
+
+    // Proofs longer than the deepest tree are rejected instead of overflowing the index.
+    let too_long = vec![[0; 32]; MAX_DEPTH as usize + 10];
+    let root = compute_root(&[2; 32], u32::MAX, &too_long);
+    assert!(!verify_proof(&root, &[2; 32], u32::MAX, &too_long));
+    // Indexes past the leaves of the proof's depth are rejected, even though the bits above the
+    // proof do not change the computed root.
+    assert_eq!(
+        compute_root(&[2; 32], 1 + (1 << DEPTH), &proof),
+        tree.get_root()
+    );
+    assert!(!verify_proof(
+        &tree.get_root(),
+        &[2; 32],
+        1 + (1 << DEPTH),
+        &proof
+    ));
+
+    // Proofs truncated by the canopy are completed from the cached nodes.
+    let canopy = replayed.canopy(2);
+    assert_eq!(canopy.len(), 6);
+    // The right half of the tree was never written.
+    assert_eq!(canopy[1], [0; 32]);
+    assert_eq!(canopy[4], [0; 32]);
+    assert_eq!(canopy[5], [0; 32]);
+    assert_eq!(canopy_depth(canopy.len()), Some(2));
+    assert_eq!(canopy_depth(5), None);
+    let truncated = truncate_proof(&proof, 2);
+    assert_eq!(truncated.len(), 1);
+    let filled = fill_proof_from_canopy(truncated, 1, DEPTH as u32, &canopy).unwrap();
+    assert_eq!(filled, proof);
+    assert!(fill_proof_from_canopy(&proof, 1, DEPTH as u32, &canopy).is_err());
+
+    // Later changelogs bring the proof up to date.
+    for leaf in 4..=6u8 {
+        tree.append([leaf; 32]).unwrap();
+        let changelog = last_changelog(id, &tree);
+        replayer.apply(&changelog).unwrap();
+        assert!(update_proof(&mut proof, 1, &changelog));
+        assert!(verify_proof(&tree.get_root(), &[2; 32], 1, &proof));
+    }
+    assert_eq!(proof, replayer.tree(&id).unwrap().proof(1).unwrap());
+
+    // A changelog whose leaf diverges above the proof leaves it untouched.
+    let mut foreign = last_changelog(id, &tree);
+    foreign.index = 1 << DEPTH;
+    let before = proof.clone();
+    assert!(!update_proof(&mut proof, 1, &foreign));
+    assert_eq!(proof, before);
+
+    tree.set_leaf(tree.get_root(), [2; 32], [7; 32], &proof, 1)
+        .unwrap();
+    assert!(!update_proof(&mut proof, 1, &last_changelog(id, &tree)));
+    assert!(verify_proof(&tree.get_root(), &[7; 32], 1, &proof));
+}